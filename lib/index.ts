@@ -1,16 +1,306 @@
 import {
 	SystemBus,
+	Subscription as NativeSubscription,
+	Inhibitor as NativeInhibitor,
+	InhibitorInfo,
+	UnitStatus,
+	UnitFileChange,
+	UnitFileInstall,
+	TransientProperties,
+	UnitProperties,
+	ServiceStatus,
+	ResourceUsage,
+	SettableUnitProperties,
+	JobInfo,
+	UnitDependencies,
+	DependencyNode,
+	listUnits,
+	listUnitFiles,
+	getUnitFileState,
+	getUnitFileLinks,
+	enableUnitFiles,
+	disableUnitFiles,
+	reenableUnitFiles,
+	maskUnitFiles,
+	unmaskUnitFiles,
+	presetUnitFiles,
+	revertUnitFiles,
+	getDefaultTarget,
+	setDefaultTarget,
+	isolate,
+	managerReload,
+	managerReexecute,
+	startTransientUnit,
+	setUnitProperties,
 	unitActiveState,
 	unitPartOf,
+	unitDependencies,
+	unitDependencyTree,
+	unitProperties,
+	serviceStatus,
+	unitResourceUsage,
+	unitSubscribe,
+	unsubscribe,
 	unitStart,
 	unitStop,
 	unitRestart,
+	unitReload,
+	unitTryRestart,
+	unitReloadOrRestart,
+	unitReloadOrTryRestart,
+	unitKill,
+	unitQueueSignal,
+	unitResetFailed,
+	managerResetFailed,
+	listJobs,
+	getJob,
+	cancelJob,
+	clearJobs,
+	jobProperties,
+	jobCancel,
+	jobAfter,
+	jobBefore,
 	powerOff,
 	reboot,
+	halt,
+	suspend,
+	hibernate,
+	hybridSleep,
+	suspendThenHibernate,
+	inhibit,
+	inhibitorRelease,
+	listInhibitors,
+	scheduleShutdown,
+	cancelScheduledShutdown,
+	setWallMessage,
+	scheduledShutdown,
+	canReboot,
+	canPowerOff,
+	canHalt,
+	canSuspend,
+	canHibernate,
+	canHybridSleep,
+	canSuspendThenHibernate,
+	setErrorClasses,
 	system,
+	peer as nativePeer,
+	close as nativeClose,
+	setConnectionListener,
+	abortHandle,
+	abort,
+	CallOptions as NativeCallOptions,
 } from '../native/index.node';
 
-export { system, SystemBus } from '../native/index.node';
+// Connections to the system bus, the session bus of the current user
+// and to a bus at an arbitrary D-Bus address. All of them can be used
+// with every class in this module
+export {
+	system,
+	session,
+	connect,
+	SystemBus,
+	UnitStatus,
+	UnitFileChange,
+	UnitFileInstall,
+	TransientProperties,
+	InhibitorInfo,
+	UnitProperties,
+	ServiceStatus,
+	ExecCommand,
+	ResourceUsage,
+	SettableUnitProperties,
+	JobInfo,
+	UnitDependencies,
+	DependencyNode,
+} from '../native/index.node';
+
+/**
+ * Error thrown when a D-Bus method call receives an error reply, e.g. when
+ * querying a unit that is not loaded or when the caller lacks permissions.
+ *
+ * The `code` property contains the D-Bus error name, see `ErrorCode` for some
+ * of the most common values.
+ */
+export class MethodError extends Error {
+	constructor(
+		message: string,
+		readonly code: string,
+	) {
+		super(message);
+		this.name = 'MethodError';
+	}
+}
+
+/**
+ * Error thrown when communication with the bus fails, e.g. when
+ * the bus socket cannot be reached or the connection is lost.
+ */
+export class TransportError extends Error {
+	constructor(message: string) {
+		super(message);
+		this.name = 'TransportError';
+	}
+}
+
+/**
+ * Error thrown when a call is aborted through the `signal` option
+ */
+export class AbortError extends Error {
+	constructor(message: string) {
+		super(message);
+		this.name = 'AbortError';
+	}
+}
+
+/**
+ * Error thrown when a call does not complete within the
+ * time given with the `timeoutMs` option
+ */
+export class TimeoutError extends Error {
+	constructor(message: string) {
+		super(message);
+		this.name = 'TimeoutError';
+	}
+}
+
+// Errors from D-Bus calls are created by the native module
+setErrorClasses(MethodError, TransportError, AbortError, TimeoutError);
+
+/**
+ * Options accepted by every call to the bus
+ */
+export interface CallOptions {
+	/**
+	 * Maximum time for the call to complete, in milliseconds, after
+	 * which it rejects with a `TimeoutError`. Waits forever if not set.
+	 */
+	timeoutMs?: number;
+
+	/**
+	 * Abort the call, rejecting it with an `AbortError`
+	 */
+	signal?: AbortSignal;
+}
+
+// Make a native call with the given options. If a signal is given, the
+// call is aborted through a native abort handle once the signal fires
+async function withOptions<T>(
+	{ timeoutMs, signal }: CallOptions,
+	call: (options: NativeCallOptions) => Promise<T>,
+): Promise<T> {
+	if (signal == null) {
+		return call({ timeoutMs });
+	}
+	if (signal.aborted) {
+		throw new AbortError('The operation was aborted');
+	}
+
+	const handle = abortHandle();
+	const onAbort = () => abort(handle);
+	signal.addEventListener('abort', onAbort);
+	try {
+		return await call({ timeoutMs, abort: handle });
+	} finally {
+		signal.removeEventListener('abort', onAbort);
+	}
+}
+
+/**
+ * Common D-Bus error names returned by systemd, to compare
+ * with `MethodError.code`
+ *
+ * See: https://github.com/systemd/systemd/blob/main/src/libsystemd/sd-bus/bus-common-errors.h
+ */
+export const ErrorCode = {
+	NoSuchUnit: 'org.freedesktop.systemd1.NoSuchUnit',
+	NoUnitForPID: 'org.freedesktop.systemd1.NoUnitForPID',
+	UnitExists: 'org.freedesktop.systemd1.UnitExists',
+	LoadFailed: 'org.freedesktop.systemd1.LoadFailed',
+	JobFailed: 'org.freedesktop.systemd1.JobFailed',
+	NoSuchJob: 'org.freedesktop.systemd1.NoSuchJob',
+	NotSubscribed: 'org.freedesktop.systemd1.NotSubscribed',
+	AlreadySubscribed: 'org.freedesktop.systemd1.AlreadySubscribed',
+	OnlyByDependency: 'org.freedesktop.systemd1.OnlyByDependency',
+	TransactionJobsConflicting:
+		'org.freedesktop.systemd1.TransactionJobsConflicting',
+	TransactionIsDestructive: 'org.freedesktop.systemd1.TransactionIsDestructive',
+	UnitMasked: 'org.freedesktop.systemd1.UnitMasked',
+	UnitGenerated: 'org.freedesktop.systemd1.UnitGenerated',
+	UnitLinked: 'org.freedesktop.systemd1.UnitLinked',
+	JobTypeNotApplicable: 'org.freedesktop.systemd1.JobTypeNotApplicable',
+	NoIsolation: 'org.freedesktop.systemd1.NoIsolation',
+	ShuttingDown: 'org.freedesktop.systemd1.ShuttingDown',
+	AccessDenied: 'org.freedesktop.DBus.Error.AccessDenied',
+	InteractiveAuthorizationRequired:
+		'org.freedesktop.DBus.Error.InteractiveAuthorizationRequired',
+	InvalidArgs: 'org.freedesktop.DBus.Error.InvalidArgs',
+	UnknownMethod: 'org.freedesktop.DBus.Error.UnknownMethod',
+	UnknownObject: 'org.freedesktop.DBus.Error.UnknownObject',
+	ServiceUnknown: 'org.freedesktop.DBus.Error.ServiceUnknown',
+	NoReply: 'org.freedesktop.DBus.Error.NoReply',
+} as const;
+
+/**
+ * Connect directly to the private socket of the systemd manager (PID 1),
+ * without going through the bus daemon. This allows talking to systemd
+ * with `ServiceManager` while dbus-daemon is not running, e.g. to restart it.
+ * Only root can connect to the private socket.
+ *
+ * The connection is peer-to-peer and only reaches the service manager,
+ * so `LoginManager` is not available on it.
+ */
+export function peer(path = '/run/systemd/private'): Promise<SystemBus> {
+	return nativePeer(path);
+}
+
+/**
+ * Connection state changes. Connections are monitored and re-established
+ * automatically, with exponential backoff, if the bus goes away (e.g. when
+ * dbus-daemon restarts). Calls made while disconnected reject with a
+ * `TransportError`, while unit subscriptions resume once reconnected.
+ */
+export type ConnectionEvent = 'disconnected' | 'reconnected';
+
+const connectionListeners = new WeakMap<
+	SystemBus,
+	Set<(event: ConnectionEvent) => void>
+>();
+
+/**
+ * Call `listener` every time the connection to the bus is lost or
+ * re-established. Returns a function that removes the listener.
+ */
+export function onConnectionEvent(
+	bus: SystemBus,
+	listener: (event: ConnectionEvent) => void,
+): () => void {
+	let listeners = connectionListeners.get(bus);
+	if (listeners == null) {
+		const registered = new Set<(event: ConnectionEvent) => void>();
+		setConnectionListener(bus, (event) => {
+			for (const l of registered) {
+				l(event as ConnectionEvent);
+			}
+		});
+		connectionListeners.set(bus, registered);
+		listeners = registered;
+	}
+
+	const added = listeners;
+	added.add(listener);
+	return () => {
+		added.delete(listener);
+	};
+}
+
+/**
+ * Close the connection to the bus. Pending calls on the connection reject
+ * with a `TransportError`, as do calls made afterwards, and unit
+ * subscriptions end. The connection is not re-established.
+ */
+export function close(bus: SystemBus): void {
+	nativeClose(bus);
+}
 
 /**
  * Convenience method to return a singleton instance of the system bus.
@@ -37,6 +327,378 @@ export class ServiceManager {
 	getUnit(name: string) {
 		return new Unit(this.bus, name);
 	}
+
+	/**
+	 * List units currently loaded in memory, including units that are
+	 * loaded because they were referenced by another unit.
+	 *
+	 * Results can be filtered by `states`, matching any of the load, active
+	 * or sub states of the unit, and by `patterns`, matching the unit name
+	 * using shell-style globs, e.g. `['*.service']`.
+	 *
+	 * See: https://www.freedesktop.org/software/systemd/man/org.freedesktop.systemd1.html
+	 */
+	listUnits({
+		states = [],
+		patterns = [],
+		...options
+	}: {
+		states?: string[];
+		patterns?: string[];
+	} & CallOptions = {}): Promise<UnitStatus[]> {
+		return withOptions(options, (opts) =>
+			listUnits(this.bus, states, patterns, opts),
+		);
+	}
+
+	/**
+	 * List the unit files installed on the system and their enablement state,
+	 * whether the units are loaded or not.
+	 *
+	 * Results can be filtered by `states`, e.g. `['enabled', 'static']`, and by
+	 * `patterns`, matching the unit file name using shell-style globs.
+	 *
+	 * See: https://www.freedesktop.org/software/systemd/man/org.freedesktop.systemd1.html
+	 */
+	async listUnitFiles({
+		states = [],
+		patterns = [],
+		...options
+	}: {
+		states?: UnitFileState[];
+		patterns?: string[];
+	} & CallOptions = {}): Promise<UnitFile[]> {
+		return (await withOptions(options, (opts) =>
+			listUnitFiles(this.bus, states, patterns, opts),
+		)) as UnitFile[];
+	}
+
+	/**
+	 * Return the enablement state of a unit file, given by name, e.g.
+	 * `openvpn.service`. Rejects with a `MethodError` if the unit file
+	 * does not exist.
+	 */
+	async getUnitFileState(
+		file: string,
+		options: CallOptions = {},
+	): Promise<UnitFileState> {
+		return (await withOptions(options, (opts) =>
+			getUnitFileState(this.bus, file, opts),
+		)) as UnitFileState;
+	}
+
+	/**
+	 * Return the symlinks that enabling the unit file created, i.e. the links
+	 * under /etc (or /run if `runtime` is set) pointing to the unit file.
+	 */
+	getUnitFileLinks(
+		name: string,
+		runtime = false,
+		options: CallOptions = {},
+	): Promise<string[]> {
+		return withOptions(options, (opts) =>
+			getUnitFileLinks(this.bus, name, runtime, opts),
+		);
+	}
+
+	/**
+	 * Enable one or more units in the system, by creating symlinks to them
+	 * in /etc or /run, according to the `[Install]` section of the unit file.
+	 *
+	 * Takes a list of unit file names or absolute paths. If `runtime` is true
+	 * the unit is enabled only until the next reboot. If `force` is true,
+	 * symlinks pointing to other units will be replaced.
+	 *
+	 * See: https://www.freedesktop.org/software/systemd/man/org.freedesktop.systemd1.html
+	 */
+	enableUnitFiles(
+		files: string[],
+		{ runtime = false, force = false, ...options }: UnitFileOptions = {},
+	): Promise<UnitFileInstall> {
+		return withOptions(options, (opts) =>
+			enableUnitFiles(this.bus, files, runtime, force, opts),
+		);
+	}
+
+	/**
+	 * Disable one or more units in the system, by removing symlinks to them
+	 * from /etc or /run.
+	 *
+	 * See: https://www.freedesktop.org/software/systemd/man/org.freedesktop.systemd1.html
+	 */
+	disableUnitFiles(
+		files: string[],
+		{ runtime = false, ...options }: Omit<UnitFileOptions, 'force'> = {},
+	): Promise<UnitFileChange[]> {
+		return withOptions(options, (opts) =>
+			disableUnitFiles(this.bus, files, runtime, opts),
+		);
+	}
+
+	/**
+	 * Disable and re-enable one or more units in the system.
+	 *
+	 * See: https://www.freedesktop.org/software/systemd/man/org.freedesktop.systemd1.html
+	 */
+	reenableUnitFiles(
+		files: string[],
+		{ runtime = false, force = false, ...options }: UnitFileOptions = {},
+	): Promise<UnitFileInstall> {
+		return withOptions(options, (opts) =>
+			reenableUnitFiles(this.bus, files, runtime, force, opts),
+		);
+	}
+
+	/**
+	 * Mask one or more units in the system, by symlinking them to /dev/null.
+	 * Masked units cannot be started, not even manually.
+	 *
+	 * See: https://www.freedesktop.org/software/systemd/man/org.freedesktop.systemd1.html
+	 */
+	maskUnitFiles(
+		files: string[],
+		{ runtime = false, force = false, ...options }: UnitFileOptions = {},
+	): Promise<UnitFileChange[]> {
+		return withOptions(options, (opts) =>
+			maskUnitFiles(this.bus, files, runtime, force, opts),
+		);
+	}
+
+	/**
+	 * Unmask one or more previously masked units.
+	 *
+	 * See: https://www.freedesktop.org/software/systemd/man/org.freedesktop.systemd1.html
+	 */
+	unmaskUnitFiles(
+		files: string[],
+		{ runtime = false, ...options }: Omit<UnitFileOptions, 'force'> = {},
+	): Promise<UnitFileChange[]> {
+		return withOptions(options, (opts) =>
+			unmaskUnitFiles(this.bus, files, runtime, opts),
+		);
+	}
+
+	/**
+	 * Enable or disable one or more units according to the preset
+	 * policy files.
+	 *
+	 * See: https://www.freedesktop.org/software/systemd/man/systemd.preset.html
+	 */
+	presetUnitFiles(
+		files: string[],
+		{ runtime = false, force = false, ...options }: UnitFileOptions = {},
+	): Promise<UnitFileInstall> {
+		return withOptions(options, (opts) =>
+			presetUnitFiles(this.bus, files, runtime, force, opts),
+		);
+	}
+
+	/**
+	 * Revert one or more units to the vendor version, removing any drop-in
+	 * configuration, overrides in /etc and masks.
+	 *
+	 * See: https://www.freedesktop.org/software/systemd/man/org.freedesktop.systemd1.html
+	 */
+	revertUnitFiles(
+		files: string[],
+		options: CallOptions = {},
+	): Promise<UnitFileChange[]> {
+		return withOptions(options, (opts) =>
+			revertUnitFiles(this.bus, files, opts),
+		);
+	}
+
+	/**
+	 * Return the target the system boots into, e.g. `multi-user.target`
+	 */
+	getDefaultTarget(options: CallOptions = {}): Promise<string> {
+		return withOptions(options, (opts) => getDefaultTarget(this.bus, opts));
+	}
+
+	/**
+	 * Set the target the system boots into, i.e. `systemctl set-default`. If
+	 * `force` is true, an existing `default.target` symlink is replaced.
+	 */
+	setDefaultTarget(
+		target: string,
+		{ force = false, ...options }: Omit<UnitFileOptions, 'runtime'> = {},
+	): Promise<UnitFileChange[]> {
+		return withOptions(options, (opts) =>
+			setDefaultTarget(this.bus, target, force, opts),
+		);
+	}
+
+	/**
+	 * Switch to `target`, starting it and its dependencies and stopping all
+	 * other units, i.e. `systemctl isolate`. Resolves once the job finishes
+	 * with the units that were active before and were stopped by the switch.
+	 *
+	 * The list of stopped units is best-effort. It is the difference between
+	 * the active units before and after the job, so a unit that stops for an
+	 * unrelated reason while the switch is in progress is included as well.
+	 *
+	 * Rejects with a `JobError` if the job result is not `done`.
+	 */
+	async isolate(target: string, options: CallOptions = {}): Promise<string[]> {
+		const { result, stopped } = await withOptions(options, (opts) =>
+			isolate(this.bus, target, opts),
+		);
+		assertJobDone(target, result);
+		return stopped;
+	}
+
+	/**
+	 * Reload all unit files and re-run generators, i.e. `systemctl daemon-reload`.
+	 *
+	 * Resolves once the manager reports that it finished reloading its
+	 * configuration, so units can be started right after. Use `timeoutMs`
+	 * to limit the time to wait for the reload to finish.
+	 *
+	 * See: https://www.freedesktop.org/software/systemd/man/org.freedesktop.systemd1.html
+	 */
+	async reload(options: CallOptions = {}): Promise<void> {
+		await withOptions(options, (opts) => managerReload(this.bus, opts));
+	}
+
+	/**
+	 * Serialize the manager state, re-execute the manager binary and
+	 * deserialize the state again, i.e. `systemctl daemon-reexec`.
+	 *
+	 * Resolves once the re-executed manager reports that it finished
+	 * reloading its configuration.
+	 *
+	 * See: https://www.freedesktop.org/software/systemd/man/org.freedesktop.systemd1.html
+	 */
+	async reexecute(options: CallOptions = {}): Promise<void> {
+		await withOptions(options, (opts) => managerReexecute(this.bus, opts));
+	}
+
+	/**
+	 * Create and start a transient unit, i.e. a unit that is not backed by a
+	 * unit file and is released once it stops, in the same way as `systemd-run`.
+	 *
+	 * Auxiliary units can be passed with `opts.aux` to be created along with the
+	 * main unit, e.g. a `.timer` or `.socket` unit.
+	 *
+	 * Returns once the start job is enqueued, use `opts.wait` to wait for the
+	 * start job to finish.
+	 *
+	 * Example:
+	 * ```
+	 * await manager.startTransientUnit('cleanup.service', {
+	 * 	execStart: ['/usr/bin/cleanup', '--all'],
+	 * 	memoryMax: 64 * 1024 * 1024,
+	 * 	cpuQuota: 20,
+	 * });
+	 * ```
+	 *
+	 * See: https://www.freedesktop.org/software/systemd/man/org.freedesktop.systemd1.html
+	 */
+	async startTransientUnit(
+		name: string,
+		properties: TransientProperties,
+		{
+			mode = 'fail',
+			aux = [],
+			...opts
+		}: JobOptions & {
+			mode?: JobMode;
+			aux?: Array<{ name: string; properties: TransientProperties }>;
+		} = {},
+	): Promise<Job> {
+		const job = await withOptions(opts, (callOpts) =>
+			startTransientUnit(
+				this.bus,
+				name,
+				mode,
+				properties,
+				aux,
+				!!opts.wait,
+				callOpts,
+			),
+		);
+		assertJobDone(name, job.result);
+		return new Job(this.bus, job.path);
+	}
+
+	/**
+	 * Reset the failed state of all units, as well as their restart
+	 * counters, i.e. `systemctl reset-failed`
+	 */
+	async resetFailed(options: CallOptions = {}): Promise<void> {
+		await withOptions(options, (opts) => managerResetFailed(this.bus, opts));
+	}
+
+	/**
+	 * List the jobs currently queued or running
+	 */
+	listJobs(options: CallOptions = {}): Promise<JobInfo[]> {
+		return withOptions(options, (opts) => listJobs(this.bus, opts));
+	}
+
+	/**
+	 * Return a handle to the job with the given id. Rejects with a
+	 * `MethodError` if no such job exists, e.g. because it already finished.
+	 */
+	async getJob(id: number, options: CallOptions = {}): Promise<Job> {
+		const path = await withOptions(options, (opts) =>
+			getJob(this.bus, id, opts),
+		);
+		return new Job(this.bus, path);
+	}
+
+	/**
+	 * Cancel the job with the given id
+	 */
+	async cancelJob(id: number, options: CallOptions = {}): Promise<void> {
+		await withOptions(options, (opts) => cancelJob(this.bus, id, opts));
+	}
+
+	/**
+	 * Cancel all queued jobs, i.e. `systemctl cancel` with no arguments
+	 */
+	async clearJobs(options: CallOptions = {}): Promise<void> {
+		await withOptions(options, (opts) => clearJobs(this.bus, opts));
+	}
+}
+
+/**
+ * Enablement state of a unit file, as shown by `systemctl is-enabled`
+ *
+ * See: https://www.freedesktop.org/software/systemd/man/systemctl.html#is-enabled%20UNIT%E2%80%A6
+ */
+export type UnitFileState =
+	| 'enabled'
+	| 'enabled-runtime'
+	| 'linked'
+	| 'linked-runtime'
+	| 'alias'
+	| 'masked'
+	| 'masked-runtime'
+	| 'static'
+	| 'indirect'
+	| 'disabled'
+	| 'generated'
+	| 'transient'
+	| 'bad';
+
+export interface UnitFile {
+	/** Absolute path of the unit file */
+	path: string;
+	state: UnitFileState;
+}
+
+export interface UnitFileOptions extends CallOptions {
+	/**
+	 * Only apply the change until the next reboot, i.e. apply
+	 * the changes under /run instead of /etc. Defaults to `false`.
+	 */
+	runtime?: boolean;
+
+	/**
+	 * Replace symlinks pointing to other units. Defaults to `false`.
+	 */
+	force?: boolean;
 }
 
 /**
@@ -48,6 +710,162 @@ export class ServiceManager {
  */
 export type JobMode = 'replace' | 'fail' | 'isolate' | 'ignore-dependencies';
 
+/**
+ * Result of a finished job
+ *
+ * From: https://www.freedesktop.org/software/systemd/man/org.freedesktop.systemd1.html
+ *
+ * > done indicates successful execution of a job. canceled indicates that a job has been canceled before it finished execution. timeout indicates that the job timeout was reached. failed indicates that the job failed. dependency indicates that a job this job depended on failed and the job hence was removed as well. skipped indicates that a job was skipped because it didn't apply to the unit's current state.
+ */
+export type JobResult =
+	| 'done'
+	| 'canceled'
+	| 'timeout'
+	| 'failed'
+	| 'dependency'
+	| 'skipped';
+
+/**
+ * Options of calls enqueueing a job. If `wait` is set, `timeoutMs`
+ * includes the time waiting for the job to finish.
+ */
+export interface JobOptions extends CallOptions {
+	/**
+	 * Wait for the job to finish before resolving. If the job
+	 * finishes with a result other than `done`, the call will reject with
+	 * a `JobError`. Defaults to `false`.
+	 */
+	wait?: boolean;
+}
+
+/**
+ * Error thrown when waiting for a job that does not
+ * finish with the `done` result
+ */
+export class JobError extends Error {
+	constructor(
+		readonly unit: string,
+		readonly result: JobResult,
+	) {
+		super(`Job for unit ${unit} finished with result '${result}'`);
+		this.name = 'JobError';
+	}
+}
+
+function assertJobDone(unit: string, result?: string) {
+	if (result != null && result !== 'done') {
+		throw new JobError(unit, result as JobResult);
+	}
+}
+
+/**
+ * A job enqueued by the manager. Job objects are removed from the bus once
+ * the job finishes, after which calls on the handle reject with a `MethodError`.
+ *
+ * See: https://www.freedesktop.org/software/systemd/man/org.freedesktop.systemd1.html#Job%20Objects
+ */
+export class Job {
+	constructor(
+		readonly bus: SystemBus,
+		readonly path: string,
+	) {}
+
+	/**
+	 * Return the job id, unit, type (e.g. `start`) and state, i.e. `waiting`
+	 * or `running`
+	 */
+	get properties(): Promise<JobInfo> {
+		return this.getProperties();
+	}
+
+	/**
+	 * Same as `properties`, with call options
+	 */
+	getProperties(options: CallOptions = {}): Promise<JobInfo> {
+		return withOptions(options, (opts) =>
+			jobProperties(this.bus, this.path, opts),
+		);
+	}
+
+	/**
+	 * Cancel the job
+	 */
+	async cancel(options: CallOptions = {}): Promise<void> {
+		await withOptions(options, (opts) => jobCancel(this.bus, this.path, opts));
+	}
+
+	/**
+	 * Return the jobs this job is waiting for, i.e. the jobs it is
+	 * ordered after
+	 */
+	getAfter(options: CallOptions = {}): Promise<JobInfo[]> {
+		return withOptions(options, (opts) => jobAfter(this.bus, this.path, opts));
+	}
+
+	/**
+	 * Return the jobs waiting for this job to finish, i.e. the jobs
+	 * it is ordered before
+	 */
+	getBefore(options: CallOptions = {}): Promise<JobInfo[]> {
+		return withOptions(options, (opts) =>
+			jobBefore(this.bus, this.path, opts),
+		);
+	}
+}
+
+/**
+ * Processes of a unit to send a signal to, the main process, the control
+ * process (e.g. `ExecReload=`) or all processes of the unit
+ */
+export type KillWhom = 'main' | 'control' | 'all';
+
+/**
+ * Signal name, e.g. `SIGTERM`, `SIGRTMIN+1`, or signal number
+ */
+export type Signal = string | number;
+
+/**
+ * Kind of dependencies to follow when walking the dependency tree of a unit.
+ * `requires` follows requirement dependencies (`Requires=`, `Requisite=`,
+ * `Wants=`, `ConsistsOf=`, `BindsTo=` and `Upholds=`), while `before`
+ * and `after` follow ordering dependencies.
+ */
+export type DependencyKind = 'requires' | 'before' | 'after';
+
+export interface DependencyTreeOptions extends CallOptions {
+	/** Kind of dependencies to follow. Defaults to `requires` */
+	kind?: DependencyKind;
+
+	/**
+	 * Follow the dependencies in reverse, i.e. show the units that depend on
+	 * this unit. Defaults to `false`
+	 */
+	reverse?: boolean;
+
+	/** Maximum number of levels to walk. Defaults to no limit */
+	depth?: number;
+}
+
+export interface UnitState {
+	activeState: string;
+	subState: string;
+}
+
+/**
+ * A subscription to unit state changes
+ */
+export class Subscription {
+	constructor(private readonly handle: NativeSubscription) {}
+
+	/**
+	 * Stop receiving state changes. This removes the D-Bus match rule and
+	 * releases the manager subscription.
+	 */
+	async unsubscribe(): Promise<void> {
+		await unsubscribe(this.handle);
+	}
+}
+
 export class Unit {
 	constructor(
 		readonly bus: SystemBus,
@@ -62,7 +880,16 @@ export class Unit {
 	 * > ActiveState contains a state value that reflects whether the unit is currently active or not. The following states are currently defined: active, reloading, inactive, failed, activating, deactivating. active indicates that unit is active (obviously...). reloading indicates that the unit is active and currently reloading its configuration. inactive indicates that it is inactive and the previous run was successful or no previous run has taken place yet. failed indicates that it is inactive and the previous run was not successful (more information about the reason for this is available on the unit type specific interfaces, for example for services in the Result property, see below). activating indicates that the unit has previously been inactive but is currently in the process of entering an active state. Conversely deactivating indicates that the unit is currently in the process of deactivation.
 	 */
 	get activeState(): Promise<string> {
-		return unitActiveState(this.bus, this.name);
+		return this.getActiveState();
+	}
+
+	/**
+	 * Same as `activeState`, with call options
+	 */
+	getActiveState(options: CallOptions = {}): Promise<string> {
+		return withOptions(options, (opts) =>
+			unitActiveState(this.bus, this.name, opts),
+		);
 	}
 
 	/**
@@ -72,7 +899,144 @@ export class Unit {
 	 * https://www.freedesktop.org/software/systemd/man/systemd.unit.html#PartOf=
 	 */
 	get partOf(): Promise<string[]> {
-		return unitPartOf(this.bus, this.name);
+		return this.getPartOf();
+	}
+
+	/**
+	 * Same as `partOf`, with call options
+	 */
+	getPartOf(options: CallOptions = {}): Promise<string[]> {
+		return withOptions(options, (opts) =>
+			unitPartOf(this.bus, this.name, opts),
+		);
+	}
+
+	/**
+	 * Return all dependency lists of the unit, e.g. `requires`, `wantedBy` or `after`
+	 *
+	 * See: https://www.freedesktop.org/software/systemd/man/systemd.unit.html#%5BUnit%5D%20Section%20Options
+	 */
+	get dependencies(): Promise<UnitDependencies> {
+		return this.getDependencies();
+	}
+
+	/**
+	 * Same as `dependencies`, with call options
+	 */
+	getDependencies(options: CallOptions = {}): Promise<UnitDependencies> {
+		return withOptions(options, (opts) =>
+			unitDependencies(this.bus, this.name, opts),
+		);
+	}
+
+	/**
+	 * Walk the dependencies of the unit recursively, i.e. `systemctl list-dependencies`.
+	 * Each unit is only expanded the first time it is found in the tree, later
+	 * occurrences are returned without children.
+	 *
+	 * ```
+	 * const tree = await unit.dependencyTree({ reverse: true, depth: 2 });
+	 * ```
+	 */
+	dependencyTree({
+		kind = 'requires',
+		reverse = false,
+		depth,
+		...options
+	}: DependencyTreeOptions = {}): Promise<DependencyNode> {
+		return withOptions(options, (opts) =>
+			unitDependencyTree(this.bus, this.name, kind, reverse, depth, opts),
+		);
+	}
+
+	/**
+	 * Return a snapshot of the unit properties, similar to `systemctl show`,
+	 * fetched with a single D-Bus call. The unit is loaded if needed, so this
+	 * also works for units that are not loaded, e.g. inactive or unknown units.
+	 *
+	 * Properties not supported by the running systemd version are returned as `null`.
+	 * Timestamps of events that have not happened yet are also `null`.
+	 *
+	 * See: https://www.freedesktop.org/software/systemd/man/org.freedesktop.systemd1.html
+	 */
+	get properties(): Promise<UnitProperties> {
+		return this.getProperties();
+	}
+
+	/**
+	 * Same as `properties`, with call options
+	 */
+	getProperties(options: CallOptions = {}): Promise<UnitProperties> {
+		return withOptions(options, (opts) =>
+			unitProperties(this.bus, this.name, opts),
+		);
+	}
+
+	/**
+	 * Return the service specific properties of the unit, e.g. the main process
+	 * id or the `Result` explaining why a service failed. Only valid
+	 * for `.service` units.
+	 *
+	 * `execMainCode` and `execMainStatus` follow the `si_code` and `si_status`
+	 * semantics of waitid(2), i.e. for a process that exited (`code` 1) the
+	 * status is the exit code, while for a killed process it is the signal number.
+	 *
+	 * See: https://www.freedesktop.org/software/systemd/man/org.freedesktop.systemd1.html#Service%20Unit%20Objects
+	 */
+	get serviceStatus(): Promise<ServiceStatus> {
+		return this.getServiceStatus();
+	}
+
+	/**
+	 * Same as `serviceStatus`, with call options
+	 */
+	getServiceStatus(options: CallOptions = {}): Promise<ServiceStatus> {
+		return withOptions(options, (opts) =>
+			serviceStatus(this.bus, this.name, opts),
+		);
+	}
+
+	/**
+	 * Return the resource usage of the unit control group as tracked by
+	 * systemd. Only valid for units with a control group, i.e. services,
+	 * slices, scopes, sockets, mounts and swaps.
+	 *
+	 * Counters are `null` if the corresponding accounting (e.g. `MemoryAccounting`)
+	 * is disabled for the unit or the kernel does not provide the value.
+	 *
+	 * See: https://www.freedesktop.org/software/systemd/man/systemd.resource-control.html
+	 */
+	get resourceUsage(): Promise<ResourceUsage> {
+		return this.getResourceUsage();
+	}
+
+	/**
+	 * Same as `resourceUsage`, with call options
+	 */
+	getResourceUsage(options: CallOptions = {}): Promise<ResourceUsage> {
+		return withOptions(options, (opts) =>
+			unitResourceUsage(this.bus, this.name, opts),
+		);
+	}
+
+	/**
+	 * Subscribe to changes of the unit `ActiveState` and `SubState`. The callback
+	 * is called with both states every time one of them changes.
+	 *
+	 * The subscription keeps the Node.js process alive, call `unsubscribe()` on
+	 * the returned subscription to stop receiving changes. The `options` only
+	 * apply to setting up the subscription.
+	 *
+	 * See: https://www.freedesktop.org/software/systemd/man/org.freedesktop.systemd1.html
+	 */
+	async subscribe(
+		callback: (state: UnitState) => void,
+		options: CallOptions = {},
+	): Promise<Subscription> {
+		const handle = await withOptions(options, (opts) =>
+			unitSubscribe(this.bus, this.name, callback, opts),
+		);
+		return new Subscription(handle);
 	}
 
 	/**
@@ -82,9 +1046,16 @@ export class Unit {
 	 * From: https://www.freedesktop.org/wiki/Software/systemd/dbus/
 	 *
 	 * > The mode needs to be one of replace, fail, isolate, ignore-dependencies, ignore-requirements. If "replace" the call will start the unit and its dependencies, possibly replacing already queued jobs that conflict with this. If "fail" the call will start the unit and its dependencies, but will fail if this would change an already queued job. If "isolate" the call will start the unit in question and terminate all units that aren't dependencies of it. If "ignore-dependencies" it will start a unit but ignore all its dependencies. If "ignore-requirements" it will start a unit but only ignore the requirement dependencies. It is not recommended to make use of the latter two options. Returns the newly created job object.
+	 *
+	 * By default the call resolves as soon as the job is enqueued, use `opts.wait`
+	 * to wait for the job to finish.
 	 */
-	async start(mode: JobMode = 'fail'): Promise<void> {
-		await unitStart(this.bus, this.name, mode);
+	async start(mode: JobMode = 'fail', opts: JobOptions = {}): Promise<Job> {
+		const job = await withOptions(opts, (callOpts) =>
+			unitStart(this.bus, this.name, mode, !!opts.wait, callOpts),
+		);
+		assertJobDone(this.name, job.result);
+		return new Job(this.bus, job.path);
 	}
 
 	/**
@@ -95,22 +1066,231 @@ export class Unit {
 	 * @see: https://www.freedesktop.org/wiki/Software/systemd/dbus/
 	 * @see Unit.star
 	 */
-	async stop(mode: JobMode = 'fail'): Promise<void> {
-		await unitStop(this.bus, this.name, mode);
+	async stop(mode: JobMode = 'fail', opts: JobOptions = {}): Promise<Job> {
+		const job = await withOptions(opts, (callOpts) =>
+			unitStop(this.bus, this.name, mode, !!opts.wait, callOpts),
+		);
+		assertJobDone(this.name, job.result);
+		return new Job(this.bus, job.path);
 	}
 
 	/**
-	 * Enqueues a stop job and possibly dependent jobs.
+	 * Enqueues a restart job and possibly dependent jobs.
 	 *
 	 * This defaults to `fail` mode
 	 *
 	 * See: https://www.freedesktop.org/wiki/Software/systemd/dbus/
 	 */
-	async restart(mode: JobMode = 'fail'): Promise<void> {
-		await unitRestart(this.bus, this.name, mode);
+	async restart(mode: JobMode = 'fail', opts: JobOptions = {}): Promise<Job> {
+		const job = await withOptions(opts, (callOpts) =>
+			unitRestart(this.bus, this.name, mode, !!opts.wait, callOpts),
+		);
+		assertJobDone(this.name, job.result);
+		return new Job(this.bus, job.path);
+	}
+
+	/**
+	 * Enqueues a reload job, i.e. asks the unit to reload its configuration
+	 * without restarting. Fails if the unit does not support reloading.
+	 *
+	 * This defaults to `fail` mode
+	 *
+	 * See: https://www.freedesktop.org/wiki/Software/systemd/dbus/
+	 */
+	async reload(mode: JobMode = 'fail', opts: JobOptions = {}): Promise<Job> {
+		const job = await withOptions(opts, (callOpts) =>
+			unitReload(this.bus, this.name, mode, !!opts.wait, callOpts),
+		);
+		assertJobDone(this.name, job.result);
+		return new Job(this.bus, job.path);
+	}
+
+	/**
+	 * Enqueues a restart job if the unit is running, a stopped
+	 * unit is left stopped, i.e. `systemctl try-restart`.
+	 *
+	 * This defaults to `fail` mode
+	 */
+	async tryRestart(
+		mode: JobMode = 'fail',
+		opts: JobOptions = {},
+	): Promise<Job> {
+		const job = await withOptions(opts, (callOpts) =>
+			unitTryRestart(this.bus, this.name, mode, !!opts.wait, callOpts),
+		);
+		assertJobDone(this.name, job.result);
+		return new Job(this.bus, job.path);
+	}
+
+	/**
+	 * Enqueues a reload job if the unit supports reloading, or a restart
+	 * job otherwise, i.e. `systemctl reload-or-restart`. A stopped unit
+	 * is started.
+	 *
+	 * This defaults to `fail` mode
+	 */
+	async reloadOrRestart(
+		mode: JobMode = 'fail',
+		opts: JobOptions = {},
+	): Promise<Job> {
+		const job = await withOptions(opts, (callOpts) =>
+			unitReloadOrRestart(this.bus, this.name, mode, !!opts.wait, callOpts),
+		);
+		assertJobDone(this.name, job.result);
+		return new Job(this.bus, job.path);
+	}
+
+	/**
+	 * Enqueues a reload job if the unit supports reloading, or a restart
+	 * job if the unit is running, i.e. `systemctl try-reload-or-restart`.
+	 * A stopped unit is left stopped.
+	 *
+	 * This defaults to `fail` mode
+	 */
+	async reloadOrTryRestart(
+		mode: JobMode = 'fail',
+		opts: JobOptions = {},
+	): Promise<Job> {
+		const job = await withOptions(opts, (callOpts) =>
+			unitReloadOrTryRestart(this.bus, this.name, mode, !!opts.wait, callOpts),
+		);
+		assertJobDone(this.name, job.result);
+		return new Job(this.bus, job.path);
+	}
+
+	/**
+	 * Send a signal to the processes of the unit, i.e. `systemctl kill`. Signals
+	 * can be given by name (e.g. `SIGHUP`) or number.
+	 */
+	async kill(
+		signal: Signal = 'SIGTERM',
+		whom: KillWhom = 'all',
+		options: CallOptions = {},
+	): Promise<void> {
+		await withOptions(options, (opts) =>
+			unitKill(this.bus, this.name, whom, signal, opts),
+		);
+	}
+
+	/**
+	 * Queue a signal with an integer `value` to the processes of the unit using
+	 * sigqueue(3), i.e. `systemctl kill --kill-value=`. This is mostly useful
+	 * with real-time signals, e.g. `SIGRTMIN+1`.
+	 */
+	async queueSignal(
+		signal: Signal,
+		value: number,
+		whom: KillWhom = 'main',
+		options: CallOptions = {},
+	): Promise<void> {
+		await withOptions(options, (opts) =>
+			unitQueueSignal(this.bus, this.name, whom, signal, value, opts),
+		);
+	}
+
+	/**
+	 * Reset the failed state of the unit as well as its restart
+	 * counter, i.e. `systemctl reset-failed <unit>`
+	 */
+	async resetFailed(options: CallOptions = {}): Promise<void> {
+		await withOptions(options, (opts) =>
+			unitResetFailed(this.bus, this.name, opts),
+		);
+	}
+
+	/**
+	 * Change resource control properties of the unit, e.g. to limit the memory or
+	 * CPU available to a running service, i.e. `systemctl set-property`.
+	 *
+	 * Properties are validated before calling systemd and no changes are applied
+	 * if validation fails. Unknown properties or values of the wrong type reject
+	 * with a `TypeError`, numbers out of range for the property with a
+	 * `RangeError`.
+	 *
+	 * Changes are persisted unless `runtime` is set, in which case they are lost
+	 * on the next reboot.
+	 *
+	 * ```
+	 * await unit.setProperties({
+	 *   runtime: true,
+	 *   properties: { MemoryMax: 512 * 1024 * 1024, CPUQuotaPerSecUSec: 500000 },
+	 * });
+	 * ```
+	 *
+	 * See: https://www.freedesktop.org/software/systemd/man/org.freedesktop.systemd1.html#Methods
+	 */
+	async setProperties({
+		runtime = false,
+		properties,
+		...options
+	}: {
+		runtime?: boolean;
+		properties: SettableUnitProperties;
+	} & CallOptions): Promise<void> {
+		await withOptions(options, (opts) =>
+			setUnitProperties(this.bus, this.name, runtime, properties, opts),
+		);
 	}
 }
 
+/**
+ * Result of the capability queries of the LoginManager
+ *
+ * From: https://www.freedesktop.org/software/systemd/man/org.freedesktop.login1.html
+ *
+ * > If "na" is returned, the operation is not available because hardware, kernel, or drivers do not support it. If "yes" is returned, the operation is supported and the user may execute the operation without further authentication. If "no" is returned, the operation is available but the user is not allowed to execute the operation. If "challenge" is returned, the operation is available but only after authorization.
+ */
+export type Capability = 'yes' | 'no' | 'challenge' | 'na';
+
+/**
+ * Operations that can be inhibited
+ *
+ * See: https://www.freedesktop.org/software/systemd/man/org.freedesktop.login1.html
+ */
+export type InhibitWhat =
+	| 'shutdown'
+	| 'sleep'
+	| 'idle'
+	| 'handle-power-key'
+	| 'handle-suspend-key'
+	| 'handle-hibernate-key'
+	| 'handle-lid-switch';
+
+/**
+ * Inhibitor lock mode. `block` prevents the operation while the lock is
+ * held, `delay` delays it for a limited time to allow the holder to prepare
+ */
+export type InhibitMode = 'block' | 'delay' | 'block-weak';
+
+/**
+ * An inhibitor lock taken with `LoginManager.inhibit`.
+ *
+ * The lock is held until `release()` is called or the object is
+ * garbage collected.
+ */
+export class Inhibitor {
+	constructor(private readonly handle: NativeInhibitor) {}
+
+	/**
+	 * Release the lock by closing the inhibitor file descriptor.
+	 *
+	 * Returns `false` if the lock had already been released.
+	 */
+	release(): boolean {
+		return inhibitorRelease(this.handle);
+	}
+}
+
+/**
+ * Type of a scheduled shutdown
+ */
+export type ShutdownType = 'reboot' | 'poweroff' | 'halt';
+
+export interface ScheduledShutdown {
+	type: ShutdownType;
+	time: Date;
+}
+
 /**
  * See https://www.freedesktop.org/software/systemd/man/org.freedesktop.login1.html
  */
@@ -124,8 +1304,13 @@ export class LoginManager {
 	 *
 	 * From: https://www.freedesktop.org/wiki/Software/systemd/dbus/
 	 */
-	async reboot(interactive = false): Promise<void> {
-		await reboot(this.bus, interactive);
+	async reboot(
+		interactive = false,
+		options: CallOptions = {},
+	): Promise<void> {
+		await withOptions(options, (opts) =>
+			reboot(this.bus, interactive, opts),
+		);
 	}
 
 	/**
@@ -133,7 +1318,241 @@ export class LoginManager {
 	 *
 	 * This defaults to not asking for user confirmation.
 	 */
-	async powerOff(interactive = false): Promise<void> {
-		await powerOff(this.bus, interactive);
+	async powerOff(
+		interactive = false,
+		options: CallOptions = {},
+	): Promise<void> {
+		await withOptions(options, (opts) =>
+			powerOff(this.bus, interactive, opts),
+		);
+	}
+
+	/**
+	 * Halt the system, without powering it off.
+	 *
+	 * This defaults to not asking for user confirmation.
+	 */
+	async halt(interactive = false, options: CallOptions = {}): Promise<void> {
+		await withOptions(options, (opts) => halt(this.bus, interactive, opts));
+	}
+
+	/**
+	 * Suspend the system to RAM.
+	 *
+	 * This defaults to not asking for user confirmation.
+	 */
+	async suspend(
+		interactive = false,
+		options: CallOptions = {},
+	): Promise<void> {
+		await withOptions(options, (opts) =>
+			suspend(this.bus, interactive, opts),
+		);
+	}
+
+	/**
+	 * Hibernate the system to disk.
+	 *
+	 * This defaults to not asking for user confirmation.
+	 */
+	async hibernate(
+		interactive = false,
+		options: CallOptions = {},
+	): Promise<void> {
+		await withOptions(options, (opts) =>
+			hibernate(this.bus, interactive, opts),
+		);
+	}
+
+	/**
+	 * Hibernate the system to disk and suspend it to RAM, so it can resume
+	 * quickly but survives a power loss.
+	 *
+	 * This defaults to not asking for user confirmation.
+	 */
+	async hybridSleep(
+		interactive = false,
+		options: CallOptions = {},
+	): Promise<void> {
+		await withOptions(options, (opts) =>
+			hybridSleep(this.bus, interactive, opts),
+		);
+	}
+
+	/**
+	 * Suspend the system to RAM, and hibernate it after the delay
+	 * configured with `HibernateDelaySec=` in systemd-sleep.conf.
+	 *
+	 * This defaults to not asking for user confirmation.
+	 */
+	async suspendThenHibernate(
+		interactive = false,
+		options: CallOptions = {},
+	): Promise<void> {
+		await withOptions(options, (opts) =>
+			suspendThenHibernate(this.bus, interactive, opts),
+		);
+	}
+
+	/**
+	 * Take an inhibitor lock, preventing (`block` mode) or delaying (`delay` mode)
+	 * the given operations until the lock is released.
+	 *
+	 * Example:
+	 * ```
+	 * const lock = await manager.inhibit('shutdown', 'updater', 'Writing firmware');
+	 * try {
+	 * 	await writeFirmware();
+	 * } finally {
+	 * 	lock.release();
+	 * }
+	 * ```
+	 *
+	 * See: https://systemd.io/INHIBITOR_LOCKS/
+	 */
+	async inhibit(
+		what: InhibitWhat | InhibitWhat[],
+		who: string,
+		why: string,
+		mode: InhibitMode = 'block',
+		options: CallOptions = {},
+	): Promise<Inhibitor> {
+		const whats = Array.isArray(what) ? what.join(':') : what;
+		const handle = await withOptions(options, (opts) =>
+			inhibit(this.bus, whats, who, why, mode, opts),
+		);
+		return new Inhibitor(handle);
+	}
+
+	/**
+	 * Schedule a shutdown of the system at the given time, i.e. `shutdown -r <time>`.
+	 *
+	 * Logged in users are warned of the pending shutdown with the wall
+	 * message, see `setWallMessage`. Only one shutdown can be scheduled at
+	 * a time, scheduling a new one replaces the previous.
+	 */
+	async scheduleShutdown(
+		type: ShutdownType,
+		time: Date,
+		options: CallOptions = {},
+	): Promise<void> {
+		await withOptions(options, (opts) =>
+			scheduleShutdown(this.bus, type, time.getTime(), opts),
+		);
+	}
+
+	/**
+	 * Cancel a scheduled shutdown. Returns `false` if no
+	 * shutdown was scheduled.
+	 */
+	cancelScheduledShutdown(options: CallOptions = {}): Promise<boolean> {
+		return withOptions(options, (opts) =>
+			cancelScheduledShutdown(this.bus, opts),
+		);
+	}
+
+	/**
+	 * Set the message sent to logged in users when a shutdown is scheduled
+	 * or performed. If `enable` is false, no message is sent.
+	 */
+	async setWallMessage(
+		message: string,
+		enable = true,
+		options: CallOptions = {},
+	): Promise<void> {
+		await withOptions(options, (opts) =>
+			setWallMessage(this.bus, message, enable, opts),
+		);
+	}
+
+	/**
+	 * Return the currently scheduled shutdown, or `null`
+	 * if no shutdown is scheduled.
+	 */
+	get scheduledShutdown(): Promise<ScheduledShutdown | null> {
+		return this.getScheduledShutdown();
+	}
+
+	/**
+	 * Same as `scheduledShutdown`, with call options
+	 */
+	getScheduledShutdown(
+		options: CallOptions = {},
+	): Promise<ScheduledShutdown | null> {
+		return withOptions(options, (opts) =>
+			scheduledShutdown(this.bus, opts),
+		) as Promise<ScheduledShutdown | null>;
+	}
+
+	/**
+	 * List the currently active inhibitor locks
+	 */
+	listInhibitors(options: CallOptions = {}): Promise<InhibitorInfo[]> {
+		return withOptions(options, (opts) => listInhibitors(this.bus, opts));
+	}
+
+	/**
+	 * Check whether the system can be rebooted by the caller
+	 */
+	async canReboot(options: CallOptions = {}): Promise<Capability> {
+		return (await withOptions(options, (opts) =>
+			canReboot(this.bus, opts),
+		)) as Capability;
+	}
+
+	/**
+	 * Check whether the system can be powered off by the caller
+	 */
+	async canPowerOff(options: CallOptions = {}): Promise<Capability> {
+		return (await withOptions(options, (opts) =>
+			canPowerOff(this.bus, opts),
+		)) as Capability;
+	}
+
+	/**
+	 * Check whether the system can be halted by the caller
+	 */
+	async canHalt(options: CallOptions = {}): Promise<Capability> {
+		return (await withOptions(options, (opts) =>
+			canHalt(this.bus, opts),
+		)) as Capability;
+	}
+
+	/**
+	 * Check whether the system can be suspended by the caller
+	 */
+	async canSuspend(options: CallOptions = {}): Promise<Capability> {
+		return (await withOptions(options, (opts) =>
+			canSuspend(this.bus, opts),
+		)) as Capability;
+	}
+
+	/**
+	 * Check whether the system can be hibernated by the caller
+	 */
+	async canHibernate(options: CallOptions = {}): Promise<Capability> {
+		return (await withOptions(options, (opts) =>
+			canHibernate(this.bus, opts),
+		)) as Capability;
+	}
+
+	/**
+	 * Check whether the system can be put in hybrid sleep by the caller
+	 */
+	async canHybridSleep(options: CallOptions = {}): Promise<Capability> {
+		return (await withOptions(options, (opts) =>
+			canHybridSleep(this.bus, opts),
+		)) as Capability;
+	}
+
+	/**
+	 * Check whether the system can be suspended and then hibernated by the caller
+	 */
+	async canSuspendThenHibernate(
+		options: CallOptions = {},
+	): Promise<Capability> {
+		return (await withOptions(options, (opts) =>
+			canSuspendThenHibernate(this.bus, opts),
+		)) as Capability;
 	}
 }
@@ -1,9 +1,19 @@
 use neon::prelude::*;
+use neon::types::JsDate;
 use once_cell::sync::OnceCell;
+use std::collections::{HashMap, HashSet};
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 use tokio::runtime::Runtime;
+use tokio::sync::{oneshot, watch};
 use zbus::dbus_proxy;
-use zbus::export::futures_util::TryFutureExt;
-use zbus::Connection;
+use zbus::export::futures_util::future::{join_all, select, Either};
+use zbus::export::futures_util::{StreamExt, TryFutureExt};
+use zbus::fdo::{self, PropertiesChangedStream, PropertiesProxy};
+use zbus::names::InterfaceName;
+use zbus::zvariant::{ObjectPath, OwnedFd, OwnedObjectPath, OwnedValue, Value};
+use zbus::{Connection, ConnectionBuilder, DBusError, MessageStream};
 
 // Return a global tokio runtime or create one if it doesn't exist.
 // Throws a JavaScript exception if the `Runtime` fails to create.
@@ -13,6 +23,186 @@ fn runtime<'a, C: Context<'a>>(cx: &mut C) -> NeonResult<&'static Runtime> {
     RUNTIME.get_or_try_init(|| Runtime::new().or_else(|err| cx.throw_error(err.to_string())))
 }
 
+/// A unit as returned by `ListUnits`, the fields are the unit name, description,
+/// load state, active state, sub state, followed unit, unit object path,
+/// queued job id (or 0 if no job is queued), job type and job object path.
+type UnitStatus = (
+    String,
+    String,
+    String,
+    String,
+    String,
+    String,
+    OwnedObjectPath,
+    u32,
+    String,
+    OwnedObjectPath,
+);
+
+/// A change performed on the file system by unit file operations, the fields
+/// are the change type (`symlink` or `unlink`), the file name of the symlink
+/// and the destination of the symlink.
+type UnitFileChange = (String, String, String);
+
+/// Constructors for the error classes defined by the javascript module
+struct ErrorClasses {
+    method: Root<JsFunction>,
+    transport: Root<JsFunction>,
+    abort: Root<JsFunction>,
+    timeout: Root<JsFunction>,
+}
+
+// Error classes registered with `setErrorClasses` when the module is loaded
+static ERROR_CLASSES: OnceCell<ErrorClasses> = OnceCell::new();
+
+/// Register the `MethodError`, `TransportError`, `AbortError` and
+/// `TimeoutError` classes used to throw errors from D-Bus calls
+fn set_error_classes(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let method = cx.argument::<JsFunction>(0)?.root(&mut cx);
+    let transport = cx.argument::<JsFunction>(1)?.root(&mut cx);
+    let abort = cx.argument::<JsFunction>(2)?.root(&mut cx);
+    let timeout = cx.argument::<JsFunction>(3)?.root(&mut cx);
+
+    // The classes are the same for every instance of the module, so ignore
+    // the call if they have already been registered
+    let _ = ERROR_CLASSES.set(ErrorClasses {
+        method,
+        transport,
+        abort,
+        timeout,
+    });
+
+    Ok(cx.undefined())
+}
+
+// Convert a `zbus::Error` to a JavaScript exception.
+//
+// Error replies to method calls are thrown as a `MethodError`, with the D-Bus error
+// name (e.g. `org.freedesktop.systemd1.NoSuchUnit`) as the `code` property,
+// failures to communicate with the bus are thrown as a `TransportError`, and
+// anything else as a plain `Error`.
+fn throw_dbus_error<'a, C: Context<'a>, T>(cx: &mut C, err: zbus::Error) -> NeonResult<T> {
+    #[allow(deprecated)]
+    match err {
+        zbus::Error::MethodError(name, description, _) => {
+            let message = description.unwrap_or_else(|| name.to_string());
+            throw_method_error(cx, name.as_str(), message)
+        }
+        zbus::Error::FDO(err) => match *err {
+            fdo::Error::ZBus(err) => throw_dbus_error(cx, err),
+            err => {
+                let name = err.name().to_string();
+                let message = err.description().map_or_else(|| name.clone(), String::from);
+                throw_method_error(cx, &name, message)
+            }
+        },
+        zbus::Error::InputOutput(_)
+        | zbus::Error::Io(_)
+        | zbus::Error::Address(_)
+        | zbus::Error::Handshake(_)
+        | zbus::Error::InvalidGUID => throw_transport_error(cx, err.to_string()),
+        err => cx.throw_error(err.to_string()),
+    }
+}
+
+// Error of a call made through a `System`
+enum CallError {
+    DBus(zbus::Error),
+    // The connection was closed with `close` before the call completed
+    Closed,
+    // The call was aborted through its `AbortSignal`
+    Aborted,
+    // The call did not complete within its `timeoutMs`
+    TimedOut(Duration),
+}
+
+impl From<zbus::Error> for CallError {
+    fn from(err: zbus::Error) -> Self {
+        CallError::DBus(err)
+    }
+}
+
+// Throw the javascript error matching a failed call. Calls interrupted by
+// `close` reject with a `TransportError`, aborted calls with an `AbortError`
+// and calls that timed out with a `TimeoutError`
+fn throw_call_error<'a, C: Context<'a>, T, E: Into<CallError>>(
+    cx: &mut C,
+    err: E,
+) -> NeonResult<T> {
+    match err.into() {
+        CallError::DBus(err) => throw_dbus_error(cx, err),
+        CallError::Closed => throw_transport_error(cx, "connection closed".to_string()),
+        CallError::Aborted => throw_interrupted_error(
+            cx,
+            |classes| &classes.abort,
+            "The operation was aborted".to_string(),
+        ),
+        CallError::TimedOut(timeout) => throw_interrupted_error(
+            cx,
+            |classes| &classes.timeout,
+            format!("Timed out after {}ms", timeout.as_millis()),
+        ),
+    }
+}
+
+// Throw an error of the registered class returned by `class`,
+// for calls that were interrupted before completing
+fn throw_interrupted_error<'a, C: Context<'a>, T>(
+    cx: &mut C,
+    class: fn(&ErrorClasses) -> &Root<JsFunction>,
+    message: String,
+) -> NeonResult<T> {
+    let error = match ERROR_CLASSES.get() {
+        Some(classes) => {
+            let class = class(classes).to_inner(cx);
+            let message = cx.string(message);
+            class.construct(cx, [message.upcast()])?
+        }
+        None => JsError::error(cx, message)?.upcast(),
+    };
+
+    cx.throw(error)
+}
+
+// Throw a `MethodError` with the given D-Bus error name as `code`
+fn throw_method_error<'a, C: Context<'a>, T>(
+    cx: &mut C,
+    name: &str,
+    message: String,
+) -> NeonResult<T> {
+    let error = match ERROR_CLASSES.get() {
+        Some(classes) => {
+            let class = classes.method.to_inner(cx);
+            let message = cx.string(message);
+            let code = cx.string(name);
+            class.construct(cx, [message.upcast(), code.upcast()])?
+        }
+        // Fall back to a plain error if the classes have not been registered
+        None => {
+            let error = JsError::error(cx, message)?;
+            let code = cx.string(name);
+            error.set(cx, "code", code)?;
+            error.upcast()
+        }
+    };
+
+    cx.throw(error)
+}
+
+// Throw a `TransportError` with the given message
+fn throw_transport_error<'a, C: Context<'a>, T>(cx: &mut C, message: String) -> NeonResult<T> {
+    let error = match ERROR_CLASSES.get() {
+        Some(classes) => {
+            let class = classes.transport.to_inner(cx);
+            let message = cx.string(message);
+            class.construct(cx, [message.upcast()])?
+        }
+        None => JsError::error(cx, message)?.upcast(),
+    };
+
+    cx.throw(error)
+}
+
 #[dbus_proxy(
     interface = "org.freedesktop.systemd1.Manager",
     default_service = "org.freedesktop.systemd1",
@@ -22,259 +212,4231 @@ pub trait ServiceManager {
     #[dbus_proxy(object = "Unit")]
     fn get_unit(&self, unit: &str) -> zbus::Result<Unit>;
 
-    #[dbus_proxy(object = "Job")]
-    fn start_unit(&self, unit: &str, mode: &str) -> zbus::Result<Job>;
+    #[dbus_proxy(object = "Unit")]
+    fn load_unit(&self, unit: &str) -> zbus::Result<Unit>;
+
+    #[dbus_proxy(object = "Job")]
+    fn start_unit(&self, unit: &str, mode: &str) -> zbus::Result<Job>;
+
+    #[dbus_proxy(object = "Job")]
+    fn stop_unit(&self, unit: &str, mode: &str) -> zbus::Result<Job>;
+
+    #[dbus_proxy(object = "Job")]
+    fn restart_unit(&self, unit: &str, mode: &str) -> zbus::Result<Job>;
+
+    #[dbus_proxy(object = "Job")]
+    fn reload_unit(&self, unit: &str, mode: &str) -> zbus::Result<Job>;
+
+    #[dbus_proxy(object = "Job")]
+    fn try_restart_unit(&self, unit: &str, mode: &str) -> zbus::Result<Job>;
+
+    #[dbus_proxy(object = "Job")]
+    fn reload_or_restart_unit(&self, unit: &str, mode: &str) -> zbus::Result<Job>;
+
+    #[dbus_proxy(object = "Job")]
+    fn reload_or_try_restart_unit(&self, unit: &str, mode: &str) -> zbus::Result<Job>;
+
+    fn kill_unit(&self, name: &str, whom: &str, signal: i32) -> zbus::Result<()>;
+
+    fn queue_signal_unit(
+        &self,
+        name: &str,
+        whom: &str,
+        signal: i32,
+        value: i32,
+    ) -> zbus::Result<()>;
+
+    fn reset_failed_unit(&self, name: &str) -> zbus::Result<()>;
+
+    fn reset_failed(&self) -> zbus::Result<()>;
+
+    fn list_units(&self) -> zbus::Result<Vec<UnitStatus>>;
+
+    fn list_units_filtered(&self, states: &[&str]) -> zbus::Result<Vec<UnitStatus>>;
+
+    fn list_units_by_patterns(
+        &self,
+        states: &[&str],
+        patterns: &[&str],
+    ) -> zbus::Result<Vec<UnitStatus>>;
+
+    fn list_unit_files(&self) -> zbus::Result<Vec<(String, String)>>;
+
+    fn list_unit_files_by_patterns(
+        &self,
+        states: &[&str],
+        patterns: &[&str],
+    ) -> zbus::Result<Vec<(String, String)>>;
+
+    fn get_unit_file_state(&self, file: &str) -> zbus::Result<String>;
+
+    fn get_unit_file_links(&self, name: &str, runtime: bool) -> zbus::Result<Vec<String>>;
+
+    fn enable_unit_files(
+        &self,
+        files: &[&str],
+        runtime: bool,
+        force: bool,
+    ) -> zbus::Result<(bool, Vec<UnitFileChange>)>;
+
+    fn disable_unit_files(
+        &self,
+        files: &[&str],
+        runtime: bool,
+    ) -> zbus::Result<Vec<UnitFileChange>>;
+
+    fn reenable_unit_files(
+        &self,
+        files: &[&str],
+        runtime: bool,
+        force: bool,
+    ) -> zbus::Result<(bool, Vec<UnitFileChange>)>;
+
+    fn mask_unit_files(
+        &self,
+        files: &[&str],
+        runtime: bool,
+        force: bool,
+    ) -> zbus::Result<Vec<UnitFileChange>>;
+
+    fn unmask_unit_files(&self, files: &[&str], runtime: bool)
+        -> zbus::Result<Vec<UnitFileChange>>;
+
+    fn preset_unit_files(
+        &self,
+        files: &[&str],
+        runtime: bool,
+        force: bool,
+    ) -> zbus::Result<(bool, Vec<UnitFileChange>)>;
+
+    fn revert_unit_files(&self, files: &[&str]) -> zbus::Result<Vec<UnitFileChange>>;
+
+    fn get_default_target(&self) -> zbus::Result<String>;
+
+    fn set_default_target(&self, name: &str, force: bool) -> zbus::Result<Vec<UnitFileChange>>;
+
+    fn reload(&self) -> zbus::Result<()>;
+
+    fn reexecute(&self) -> zbus::Result<()>;
+
+    #[dbus_proxy(object = "Job")]
+    fn start_transient_unit(
+        &self,
+        name: &str,
+        mode: &str,
+        properties: &[(&str, Value<'_>)],
+        aux: &[(&str, &[(&str, Value<'_>)])],
+    ) -> zbus::Result<Job>;
+
+    fn set_unit_properties(
+        &self,
+        name: &str,
+        runtime: bool,
+        properties: &[(&str, Value<'_>)],
+    ) -> zbus::Result<()>;
+
+    fn list_jobs(&self) -> zbus::Result<Vec<JobInfo>>;
+
+    #[dbus_proxy(object = "Job")]
+    fn get_job(&self, id: u32) -> zbus::Result<Job>;
+
+    fn cancel_job(&self, id: u32) -> zbus::Result<()>;
+
+    fn clear_jobs(&self) -> zbus::Result<()>;
+
+    fn subscribe(&self) -> zbus::Result<()>;
+
+    fn unsubscribe(&self) -> zbus::Result<()>;
+
+    #[dbus_proxy(signal)]
+    fn job_removed(
+        &self,
+        id: u32,
+        job: OwnedObjectPath,
+        unit: String,
+        result: String,
+    ) -> zbus::Result<()>;
+
+    #[dbus_proxy(signal)]
+    fn reloading(&self, active: bool) -> zbus::Result<()>;
+}
+
+#[dbus_proxy(
+    default_service = "org.freedesktop.systemd1",
+    interface = "org.freedesktop.systemd1.Job"
+)]
+pub trait Job {
+    fn cancel(&self) -> zbus::Result<()>;
+
+    fn get_after(&self) -> zbus::Result<Vec<JobInfo>>;
+
+    fn get_before(&self) -> zbus::Result<Vec<JobInfo>>;
+
+    #[dbus_proxy(property)]
+    fn id(&self) -> zbus::Result<u32>;
+
+    #[dbus_proxy(property)]
+    fn unit(&self) -> zbus::Result<(String, OwnedObjectPath)>;
+
+    #[dbus_proxy(property)]
+    fn job_type(&self) -> zbus::Result<String>;
+
+    #[dbus_proxy(property)]
+    fn state(&self) -> zbus::Result<String>;
+}
+
+/// A job as returned by `ListJobs`, `GetAfter` and `GetBefore`, the fields
+/// are the job id, the unit name, the job type, the job state, and the
+/// job and unit object paths.
+type JobInfo = (
+    u32,
+    String,
+    String,
+    String,
+    OwnedObjectPath,
+    OwnedObjectPath,
+);
+
+#[dbus_proxy(
+    default_service = "org.freedesktop.systemd1",
+    interface = "org.freedesktop.systemd1.Unit"
+)]
+pub trait Unit {
+    #[dbus_proxy(property)]
+    fn active_state(&mut self) -> zbus::Result<String>;
+
+    #[dbus_proxy(property)]
+    fn sub_state(&mut self) -> zbus::Result<String>;
+
+    #[dbus_proxy(property)]
+    fn part_of(&mut self) -> zbus::Result<Vec<String>>;
+
+    #[dbus_proxy(property)]
+    fn requires(&self) -> zbus::Result<Vec<String>>;
+
+    #[dbus_proxy(property)]
+    fn requisite(&self) -> zbus::Result<Vec<String>>;
+
+    #[dbus_proxy(property)]
+    fn wants(&self) -> zbus::Result<Vec<String>>;
+
+    #[dbus_proxy(property)]
+    fn binds_to(&self) -> zbus::Result<Vec<String>>;
+
+    #[dbus_proxy(property)]
+    fn upholds(&self) -> zbus::Result<Vec<String>>;
+
+    #[dbus_proxy(property)]
+    fn required_by(&self) -> zbus::Result<Vec<String>>;
+
+    #[dbus_proxy(property)]
+    fn requisite_of(&self) -> zbus::Result<Vec<String>>;
+
+    #[dbus_proxy(property)]
+    fn wanted_by(&self) -> zbus::Result<Vec<String>>;
+
+    #[dbus_proxy(property)]
+    fn bound_by(&self) -> zbus::Result<Vec<String>>;
+
+    #[dbus_proxy(property)]
+    fn upheld_by(&self) -> zbus::Result<Vec<String>>;
+
+    #[dbus_proxy(property)]
+    fn consists_of(&self) -> zbus::Result<Vec<String>>;
+
+    #[dbus_proxy(property)]
+    fn conflicts(&self) -> zbus::Result<Vec<String>>;
+
+    #[dbus_proxy(property)]
+    fn before(&self) -> zbus::Result<Vec<String>>;
+
+    #[dbus_proxy(property)]
+    fn after(&self) -> zbus::Result<Vec<String>>;
+
+    #[dbus_proxy(property)]
+    fn on_failure(&self) -> zbus::Result<Vec<String>>;
+
+    #[dbus_proxy(property)]
+    fn triggers(&self) -> zbus::Result<Vec<String>>;
+
+    #[dbus_proxy(property)]
+    fn triggered_by(&self) -> zbus::Result<Vec<String>>;
+
+    #[dbus_proxy(property)]
+    fn propagates_reload_to(&self) -> zbus::Result<Vec<String>>;
+}
+
+/// An inhibitor lock as returned by `ListInhibitors`, the fields are
+/// what is inhibited, who is holding the lock, why, the lock mode,
+/// and the user and process ids of the holder.
+type InhibitorInfo = (String, String, String, String, u32, u32);
+
+/// A command executed by a service, as returned by the `ExecStart` property.
+/// The fields are the binary path, the arguments, whether failures are ignored,
+/// start and exit timestamps (realtime and monotonic), the process id, and the
+/// exit code and status of the last execution.
+type ExecCommand = (String, Vec<String>, bool, u64, u64, u64, u64, u32, i32, i32);
+
+#[dbus_proxy(
+    interface = "org.freedesktop.login1.Manager",
+    default_service = "org.freedesktop.login1",
+    default_path = "/org/freedesktop/login1"
+)]
+pub trait LoginManager {
+    fn reboot(&self, interactive: bool) -> zbus::Result<()>;
+    fn power_off(&self, interactive: bool) -> zbus::Result<()>;
+    fn halt(&self, interactive: bool) -> zbus::Result<()>;
+    fn suspend(&self, interactive: bool) -> zbus::Result<()>;
+    fn hibernate(&self, interactive: bool) -> zbus::Result<()>;
+    fn hybrid_sleep(&self, interactive: bool) -> zbus::Result<()>;
+    fn suspend_then_hibernate(&self, interactive: bool) -> zbus::Result<()>;
+
+    fn can_reboot(&self) -> zbus::Result<String>;
+    fn can_power_off(&self) -> zbus::Result<String>;
+    fn can_halt(&self) -> zbus::Result<String>;
+    fn can_suspend(&self) -> zbus::Result<String>;
+    fn can_hibernate(&self) -> zbus::Result<String>;
+    fn can_hybrid_sleep(&self) -> zbus::Result<String>;
+    fn can_suspend_then_hibernate(&self) -> zbus::Result<String>;
+
+    fn inhibit(&self, what: &str, who: &str, why: &str, mode: &str) -> zbus::Result<OwnedFd>;
+    fn list_inhibitors(&self) -> zbus::Result<Vec<InhibitorInfo>>;
+
+    fn schedule_shutdown(&self, shutdown_type: &str, usec: u64) -> zbus::Result<()>;
+    fn cancel_scheduled_shutdown(&self) -> zbus::Result<bool>;
+    fn set_wall_message(&self, wall_message: &str, enable: bool) -> zbus::Result<()>;
+
+    #[dbus_proxy(property)]
+    fn scheduled_shutdown(&self) -> zbus::Result<(String, u64)>;
+}
+
+/// Options accepted by every call
+#[derive(Default)]
+struct CallOptions {
+    // Maximum time for the call to complete
+    timeout: Option<Duration>,
+    // Set once the call is aborted from javascript
+    abort: Option<watch::Receiver<bool>>,
+}
+
+impl CallOptions {
+    // Resolve with the reason once the call should be interrupted,
+    // either because it was aborted or because it timed out
+    async fn interrupted(self) -> CallError {
+        let aborted = async move {
+            if let Some(mut abort) = self.abort {
+                // Fails if the handle was garbage collected, in
+                // which case the call can no longer be aborted
+                if abort.wait_for(|aborted| *aborted).await.is_ok() {
+                    return CallError::Aborted;
+                }
+            }
+            std::future::pending().await
+        };
+        let timed_out = async move {
+            match self.timeout {
+                Some(timeout) => {
+                    tokio::time::sleep(timeout).await;
+                    CallError::TimedOut(timeout)
+                }
+                None => std::future::pending().await,
+            }
+        };
+
+        match select(Box::pin(aborted), Box::pin(timed_out)).await {
+            Either::Left((reason, _)) | Either::Right((reason, _)) => reason,
+        }
+    }
+}
+
+// Read the call options from the object at index `i`, i.e. `timeoutMs`, in
+// milliseconds, and an `abort` handle. Both `undefined` and `null` mean that
+// no options are set.
+fn call_options(cx: &mut FunctionContext, i: i32) -> NeonResult<CallOptions> {
+    let options = match cx.argument_opt(i) {
+        Some(value) if !value.is_a::<JsUndefined, _>(cx) && !value.is_a::<JsNull, _>(cx) => {
+            value.downcast_or_throw::<JsObject, _>(cx)?
+        }
+        _ => return Ok(CallOptions::default()),
+    };
+
+    let timeout = match opt_property::<JsNumber, _>(cx, options, "timeoutMs")? {
+        Some(ms) => {
+            let ms = ms.value(cx);
+            if !ms.is_finite() || ms < 0.0 {
+                return cx
+                    .throw_range_error("timeoutMs must be a non-negative number of milliseconds");
+            }
+            Some(Duration::from_millis(ms as u64))
+        }
+        None => None,
+    };
+
+    let abort = opt_property::<JsBox<AbortHandle>, _>(cx, options, "abort")?
+        .map(|handle| handle.aborted.subscribe());
+
+    Ok(CallOptions { timeout, abort })
+}
+
+/// Aborts the calls it is passed to with the `abort` option. Created by
+/// javascript for every call made with an `AbortSignal`
+struct AbortHandle {
+    aborted: watch::Sender<bool>,
+}
+
+impl Finalize for AbortHandle {}
+
+impl AbortHandle {
+    fn create(mut cx: FunctionContext) -> JsResult<JsBox<AbortHandle>> {
+        let (aborted, _) = watch::channel(false);
+        Ok(cx.boxed(AbortHandle { aborted }))
+    }
+
+    /// Abort the calls made with the handle, rejecting them with an `AbortError`
+    fn abort(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+        let handle = cx.argument::<JsBox<AbortHandle>>(0)?;
+        handle.aborted.send_replace(true);
+        Ok(cx.undefined())
+    }
+}
+
+/// Linux signal numbers by name, for the signals that can be sent
+/// by name. Real-time signals are given as `SIGRTMIN+n` or `SIGRTMAX-n`
+const SIGNALS: &[(&str, i32)] = &[
+    ("SIGHUP", 1),
+    ("SIGINT", 2),
+    ("SIGQUIT", 3),
+    ("SIGILL", 4),
+    ("SIGTRAP", 5),
+    ("SIGABRT", 6),
+    ("SIGBUS", 7),
+    ("SIGFPE", 8),
+    ("SIGKILL", 9),
+    ("SIGUSR1", 10),
+    ("SIGSEGV", 11),
+    ("SIGUSR2", 12),
+    ("SIGPIPE", 13),
+    ("SIGALRM", 14),
+    ("SIGTERM", 15),
+    ("SIGSTKFLT", 16),
+    ("SIGCHLD", 17),
+    ("SIGCONT", 18),
+    ("SIGSTOP", 19),
+    ("SIGTSTP", 20),
+    ("SIGTTIN", 21),
+    ("SIGTTOU", 22),
+    ("SIGURG", 23),
+    ("SIGXCPU", 24),
+    ("SIGXFSZ", 25),
+    ("SIGVTALRM", 26),
+    ("SIGPROF", 27),
+    ("SIGWINCH", 28),
+    ("SIGIO", 29),
+    ("SIGPWR", 30),
+    ("SIGSYS", 31),
+];
+
+// Real-time signal range as seen by glibc programs, the first two
+// kernel real-time signals are reserved by the C library
+const SIGRTMIN: i32 = 34;
+const SIGRTMAX: i32 = 64;
+
+// Parse a signal name, with or without the `SIG` prefix
+fn parse_signal(name: &str) -> Option<i32> {
+    let name = name.strip_prefix("SIG").unwrap_or(name);
+    if let Some((_, signal)) = SIGNALS.iter().find(|(signal, _)| &signal[3..] == name) {
+        return Some(*signal);
+    }
+
+    let signal = match name {
+        "RTMIN" => SIGRTMIN,
+        "RTMAX" => SIGRTMAX,
+        _ => {
+            if let Some(n) = name.strip_prefix("RTMIN+") {
+                SIGRTMIN + n.parse::<i32>().ok()?
+            } else if let Some(n) = name.strip_prefix("RTMAX-") {
+                SIGRTMAX - n.parse::<i32>().ok()?
+            } else {
+                return None;
+            }
+        }
+    };
+
+    (SIGRTMIN..=SIGRTMAX).contains(&signal).then_some(signal)
+}
+
+// Read the signal from the argument at index `i`, either as a
+// number or a name like `SIGTERM`
+fn signal_arg(cx: &mut FunctionContext, i: i32) -> NeonResult<i32> {
+    let value = cx.argument::<JsValue>(i)?;
+    if let Ok(name) = value.downcast::<JsString, _>(cx) {
+        let name = name.value(cx);
+        return match parse_signal(&name) {
+            Some(signal) => Ok(signal),
+            None => cx.throw_type_error(format!("Unknown signal '{}'", name)),
+        };
+    }
+
+    let signal = value.downcast_or_throw::<JsNumber, _>(cx)?.value(cx);
+    if signal.fract() != 0.0 || !(1.0..=SIGRTMAX as f64).contains(&signal) {
+        return cx.throw_range_error(format!("Invalid signal number {}", signal));
+    }
+
+    Ok(signal as i32)
+}
+
+// Read the integer argument at index `i`, throwing a `RangeError` if
+// it is not an integer within the range of `T`
+fn integer_arg<T: TryFrom<i64>>(cx: &mut FunctionContext, i: i32, what: &str) -> NeonResult<T> {
+    let value = cx.argument::<JsNumber>(i)?.value(cx);
+    // The fractional part of NaN and infinity is NaN
+    if value.fract() == 0.0 {
+        if let Ok(value) = T::try_from(value as i64) {
+            return Ok(value);
+        }
+    }
+
+    cx.throw_range_error(format!("Invalid {} {}", what, value))
+}
+
+// Read the array of strings from the argument at index `i`
+fn string_array_arg(cx: &mut FunctionContext, i: i32) -> NeonResult<Vec<String>> {
+    let values = cx.argument::<JsArray>(i)?;
+    string_array(cx, values)
+}
+
+// Convert a javascript array of strings to a vector
+fn string_array<'a, C: Context<'a>>(
+    cx: &mut C,
+    values: Handle<JsArray>,
+) -> NeonResult<Vec<String>> {
+    values
+        .to_vec(cx)?
+        .into_iter()
+        .map(|value| Ok(value.downcast_or_throw::<JsString, _>(cx)?.value(cx)))
+        .collect()
+}
+
+// Read the property `key` of `obj`, returning `None` if the property
+// is `undefined` or `null` and throwing a `TypeError` if the property
+// is not of type `V`
+fn opt_property<'a, V: neon::types::Value, C: Context<'a>>(
+    cx: &mut C,
+    obj: Handle<JsObject>,
+    key: &str,
+) -> NeonResult<Option<Handle<'a, V>>> {
+    let value = obj.get_value(cx, key)?;
+    if value.is_a::<JsUndefined, _>(cx) || value.is_a::<JsNull, _>(cx) {
+        return Ok(None);
+    }
+
+    match value.downcast::<V, _>(cx) {
+        Ok(value) => Ok(Some(value)),
+        Err(_) => cx.throw_type_error(format!("Invalid value for property '{}'", key)),
+    }
+}
+
+// Read the number property `key` of `obj`, throwing a `RangeError` if the
+// value is negative or not finite, or not an integer if `integer` is set
+fn opt_non_negative<'a, C: Context<'a>>(
+    cx: &mut C,
+    obj: Handle<JsObject>,
+    key: &str,
+    integer: bool,
+) -> NeonResult<Option<f64>> {
+    let value = match opt_property::<JsNumber, _>(cx, obj, key)? {
+        Some(value) => value.value(cx),
+        None => return Ok(None),
+    };
+    if !value.is_finite() || value < 0.0 || (integer && value.fract() != 0.0) {
+        return cx.throw_range_error(format!("Invalid value for property '{}': {}", key, value));
+    }
+
+    Ok(Some(value))
+}
+
+/// Properties of a transient unit, in the structured form
+/// received from javascript
+#[derive(Default)]
+struct TransientProperties {
+    description: Option<String>,
+    service_type: Option<String>,
+    exec_start: Option<Vec<String>>,
+    environment: Option<Vec<String>>,
+    working_directory: Option<String>,
+    user: Option<String>,
+    memory_max: Option<u64>,
+    // CPU quota as a percentage of the time of one CPU
+    cpu_quota: Option<f64>,
+    runtime_max_sec: Option<f64>,
+    remain_after_exit: Option<bool>,
+}
+
+impl TransientProperties {
+    fn from_js<'a, C: Context<'a>>(cx: &mut C, obj: Handle<JsObject>) -> NeonResult<Self> {
+        let mut properties = TransientProperties::default();
+
+        if let Some(value) = opt_property::<JsString, _>(cx, obj, "description")? {
+            properties.description = Some(value.value(cx));
+        }
+        if let Some(value) = opt_property::<JsString, _>(cx, obj, "type")? {
+            properties.service_type = Some(value.value(cx));
+        }
+        if let Some(value) = opt_property::<JsArray, _>(cx, obj, "execStart")? {
+            let argv = string_array(cx, value)?;
+            if argv.is_empty() {
+                return cx.throw_type_error("execStart must contain at least the binary path");
+            }
+            properties.exec_start = Some(argv);
+        }
+        if let Some(value) = opt_property::<JsObject, _>(cx, obj, "environment")? {
+            // Environment is given as an object of variable names to values
+            let mut environment = Vec::new();
+            for key in value.get_own_property_names(cx)?.to_vec(cx)? {
+                let key = key.downcast_or_throw::<JsString, _>(cx)?;
+                let val = value.get::<JsString, _, _>(cx, key)?.value(cx);
+                environment.push(format!("{}={}", key.value(cx), val));
+            }
+            properties.environment = Some(environment);
+        }
+        if let Some(value) = opt_property::<JsString, _>(cx, obj, "workingDirectory")? {
+            properties.working_directory = Some(value.value(cx));
+        }
+        if let Some(value) = opt_property::<JsString, _>(cx, obj, "user")? {
+            properties.user = Some(value.value(cx));
+        }
+        if let Some(value) = opt_non_negative(cx, obj, "memoryMax", true)? {
+            properties.memory_max = Some(value as u64);
+        }
+        properties.cpu_quota = opt_non_negative(cx, obj, "cpuQuota", false)?;
+        properties.runtime_max_sec = opt_non_negative(cx, obj, "runtimeMaxSec", false)?;
+        if let Some(value) = opt_property::<JsBoolean, _>(cx, obj, "remainAfterExit")? {
+            properties.remain_after_exit = Some(value.value(cx));
+        }
+
+        Ok(properties)
+    }
+
+    /// Convert the properties to the `a(sv)` array expected by
+    /// `StartTransientUnit`, using the D-Bus property names and units
+    fn into_dbus(self) -> Vec<(&'static str, Value<'static>)> {
+        let mut properties = Vec::new();
+
+        if let Some(description) = self.description {
+            properties.push(("Description", Value::from(description)));
+        }
+        if let Some(service_type) = self.service_type {
+            properties.push(("Type", Value::from(service_type)));
+        }
+        if let Some(argv) = self.exec_start {
+            // ExecStart is an array of (path, argv, ignore_failure)
+            let path = argv[0].clone();
+            properties.push(("ExecStart", Value::from(vec![(path, argv, false)])));
+        }
+        if let Some(environment) = self.environment {
+            properties.push(("Environment", Value::from(environment)));
+        }
+        if let Some(working_directory) = self.working_directory {
+            properties.push(("WorkingDirectory", Value::from(working_directory)));
+        }
+        if let Some(user) = self.user {
+            properties.push(("User", Value::from(user)));
+        }
+        if let Some(memory_max) = self.memory_max {
+            properties.push(("MemoryMax", Value::U64(memory_max)));
+        }
+        if let Some(cpu_quota) = self.cpu_quota {
+            // CPUQuota= is not exposed on D-Bus, it needs to be converted to
+            // microseconds of CPU time per second the same way systemd-run does
+            let usec = (cpu_quota * 10_000.0).round() as u64;
+            properties.push(("CPUQuotaPerSecUSec", Value::U64(usec)));
+        }
+        if let Some(runtime_max_sec) = self.runtime_max_sec {
+            let usec = (runtime_max_sec * 1_000_000.0).round() as u64;
+            properties.push(("RuntimeMaxUSec", Value::U64(usec)));
+        }
+        if let Some(remain_after_exit) = self.remain_after_exit {
+            properties.push(("RemainAfterExit", Value::from(remain_after_exit)));
+        }
+
+        properties
+    }
+}
+
+/// D-Bus signature of a property that can be changed at runtime
+#[derive(Clone, Copy)]
+enum PropertyType {
+    // `t`, where `Infinity` maps to systemd's "no limit" value
+    Limit,
+    // `t`
+    U64,
+    // `b`
+    Bool,
+}
+
+/// Unit properties accepted by `SetUnitProperties`. Systemd accepts more
+/// than these, but only resource control properties are supported here
+const SETTABLE_PROPERTIES: &[(&str, PropertyType)] = &[
+    ("MemoryMin", PropertyType::Limit),
+    ("MemoryLow", PropertyType::Limit),
+    ("MemoryHigh", PropertyType::Limit),
+    ("MemoryMax", PropertyType::Limit),
+    ("MemorySwapMax", PropertyType::Limit),
+    ("TasksMax", PropertyType::Limit),
+    ("CPUQuotaPerSecUSec", PropertyType::Limit),
+    ("CPUQuotaPeriodUSec", PropertyType::U64),
+    ("CPUWeight", PropertyType::U64),
+    ("StartupCPUWeight", PropertyType::U64),
+    ("IOWeight", PropertyType::U64),
+    ("StartupIOWeight", PropertyType::U64),
+    ("CPUAccounting", PropertyType::Bool),
+    ("MemoryAccounting", PropertyType::Bool),
+    ("TasksAccounting", PropertyType::Bool),
+    ("IOAccounting", PropertyType::Bool),
+    ("IPAccounting", PropertyType::Bool),
+];
+
+/// Convert an object of unit properties, keyed by their D-Bus name,
+/// to the `a(sv)` array expected by `SetUnitProperties`. Unknown
+/// properties and values of the wrong type throw a `TypeError`, numbers
+/// out of range for the property throw a `RangeError`
+fn settable_properties_from_js<'a, C: Context<'a>>(
+    cx: &mut C,
+    obj: Handle<JsObject>,
+) -> NeonResult<Vec<(String, Value<'static>)>> {
+    let mut properties = Vec::new();
+
+    for key in obj.get_own_property_names(cx)?.to_vec(cx)? {
+        let key = key.downcast_or_throw::<JsString, _>(cx)?.value(cx);
+        let property_type = match SETTABLE_PROPERTIES.iter().find(|(name, _)| *name == key) {
+            Some((_, property_type)) => *property_type,
+            None => return cx.throw_type_error(format!("Unknown unit property '{}'", key)),
+        };
+
+        let value = match property_type {
+            PropertyType::Bool => match opt_property::<JsBoolean, _>(cx, obj, &key)? {
+                Some(value) => Value::from(value.value(cx)),
+                None => continue,
+            },
+            PropertyType::Limit | PropertyType::U64 => {
+                let value = match opt_property::<JsNumber, _>(cx, obj, &key)? {
+                    Some(value) => value.value(cx),
+                    None => continue,
+                };
+                if let (PropertyType::Limit, true) = (property_type, value == f64::INFINITY) {
+                    Value::U64(u64::MAX)
+                } else if value >= 0.0 && value.fract() == 0.0 && value < u64::MAX as f64 {
+                    Value::U64(value as u64)
+                } else {
+                    return cx.throw_range_error(format!(
+                        "Invalid value for property '{}': {}",
+                        key, value
+                    ));
+                }
+            }
+        };
+        properties.push((key, value));
+    }
+
+    Ok(properties)
+}
+
+/// A connection to the bus, along with the number of users of its manager
+/// subscription. Cloning it is cheap, and clones share the subscription
+#[derive(Clone)]
+struct BusConnection {
+    connection: Connection,
+    subscribers: Arc<tokio::sync::Mutex<usize>>,
+}
+
+impl BusConnection {
+    fn new(connection: Connection) -> Self {
+        BusConnection {
+            connection,
+            subscribers: Arc::new(tokio::sync::Mutex::new(0)),
+        }
+    }
+
+    /// Subscribe to the manager signals, i.e. `JobRemoved`, `Reloading` and
+    /// `PropertiesChanged` for units. The manager keeps a single subscription
+    /// per client, so `Subscribe` is only called for the first user of the
+    /// connection, and `Unsubscribe` once the last one releases it
+    async fn subscribe(&self) -> zbus::Result<ManagerSubscription> {
+        let mut subscribers = self.subscribers.lock().await;
+        if *subscribers == 0 {
+            let manager = ServiceManagerProxy::new(&self.connection).await?;
+            match manager.subscribe().await {
+                // Left over by a subscribe or unsubscribe call that was interrupted
+                Err(zbus::Error::MethodError(name, _, _))
+                    if name.as_str() == "org.freedesktop.systemd1.AlreadySubscribed" => {}
+                result => result?,
+            }
+        }
+        *subscribers += 1;
+
+        Ok(ManagerSubscription {
+            connection: Some(self.clone()),
+        })
+    }
+
+    async fn unsubscribe(&self) -> zbus::Result<()> {
+        let mut subscribers = self.subscribers.lock().await;
+        *subscribers -= 1;
+        if *subscribers > 0 {
+            return Ok(());
+        }
+
+        let manager = ServiceManagerProxy::new(&self.connection).await?;
+        match manager.unsubscribe().await {
+            Err(zbus::Error::MethodError(name, _, _))
+                if name.as_str() == "org.freedesktop.systemd1.NotSubscribed" =>
+            {
+                Ok(())
+            }
+            result => result,
+        }
+    }
+}
+
+impl std::ops::Deref for BusConnection {
+    type Target = Connection;
+
+    fn deref(&self) -> &Connection {
+        &self.connection
+    }
+}
+
+/// A reference to the manager subscription of a connection, released once
+/// dropped. Use `release` to wait for the manager to be unsubscribed
+struct ManagerSubscription {
+    connection: Option<BusConnection>,
+}
+
+impl ManagerSubscription {
+    async fn release(mut self) -> zbus::Result<()> {
+        match self.connection.take() {
+            Some(connection) => connection.unsubscribe().await,
+            None => Ok(()),
+        }
+    }
+}
+
+impl Drop for ManagerSubscription {
+    fn drop(&mut self) {
+        // Dropped when a call fails or is interrupted, release in the
+        // background as there is no one to report errors to
+        if let Some(connection) = self.connection.take() {
+            if let Ok(rt) = tokio::runtime::Handle::try_current() {
+                rt.spawn(async move {
+                    let _ = connection.unsubscribe().await;
+                });
+            }
+        }
+    }
+}
+
+// Error for signal streams that end before the expected signal is received,
+// which happens when the connection is lost. Thrown as a `TransportError`
+fn connection_lost(message: String) -> zbus::Error {
+    let err = std::io::Error::new(std::io::ErrorKind::ConnectionAborted, message);
+    zbus::Error::InputOutput(Arc::new(err))
+}
+
+/// Enqueue a job using the `enqueue` callback and return the job object path.
+/// If `wait` is set, wait for the manager to report the job as finished and
+/// return the job result as well, i.e. one of `done`, `canceled`, `timeout`,
+/// `failed`, `dependency` or `skipped`.
+async fn run_job<F, Fut>(
+    connection: &BusConnection,
+    wait: bool,
+    enqueue: F,
+) -> zbus::Result<(OwnedObjectPath, Option<String>)>
+where
+    F: FnOnce(ServiceManagerProxy<'static>) -> Fut,
+    Fut: Future<Output = zbus::Result<JobProxy<'static>>>,
+{
+    let manager = ServiceManagerProxy::new(connection).await?;
+    if !wait {
+        let job = enqueue(manager).await?;
+        return Ok((job.path().to_owned().into(), None));
+    }
+
+    // The manager only emits signals to subscribed clients, and we need to
+    // start listening before the job is enqueued, otherwise we might miss
+    // the signal for jobs that finish immediately
+    let _subscription = connection.subscribe().await?;
+    let mut removed = manager.receive_job_removed().await?;
+    let job = enqueue(manager).await?;
+    let job: OwnedObjectPath = job.path().to_owned().into();
+
+    let result = async {
+        while let Some(signal) = removed.next().await {
+            let args = signal.args()?;
+            if *args.job() == job {
+                return Ok(args.result().to_owned());
+            }
+        }
+        Err(connection_lost(format!(
+            "Connection lost before job {} finished",
+            job
+        )))
+    };
+
+    let result = result.await?;
+    Ok((job, Some(result)))
+}
+
+// Return the names of the units that are currently active
+async fn active_units(manager: &ServiceManagerProxy<'_>) -> zbus::Result<HashSet<String>> {
+    let units = manager.list_units().await?;
+    Ok(units
+        .into_iter()
+        .filter(|unit| unit.3 == "active" || unit.3 == "reloading")
+        .map(|unit| unit.0)
+        .collect())
+}
+
+/// Start `target` in `isolate` mode, stopping all units that are not
+/// dependencies of it, and wait for the job to finish. Returns the job
+/// result and the units that were active before the job was enqueued
+/// and are no longer active once it finished. This includes units that
+/// stopped for other reasons meanwhile, it is not limited to the stop
+/// jobs of the isolate transaction.
+async fn isolate_target(
+    connection: &BusConnection,
+    target: String,
+) -> zbus::Result<(String, Vec<String>)> {
+    let manager = ServiceManagerProxy::new(connection).await?;
+    let before = active_units(&manager).await?;
+
+    let (_, result) = run_job(connection, true, |manager| async move {
+        manager.start_unit(&target, "isolate").await
+    })
+    .await?;
+    // The result is always set when waiting for the job
+    let result = result.unwrap_or_default();
+
+    let after = active_units(&manager).await?;
+    let mut stopped: Vec<String> = before.difference(&after).cloned().collect();
+    stopped.sort();
+
+    Ok((result, stopped))
+}
+
+/// Reload or re-execute the manager and wait for it to report that
+/// it finished reloading its configuration via the `Reloading` signal.
+async fn reload_manager(connection: &BusConnection, reexecute: bool) -> zbus::Result<()> {
+    let manager = ServiceManagerProxy::new(connection).await?;
+
+    // Subscribe before reloading so we do not miss the signal
+    let _subscription = connection.subscribe().await?;
+    let mut reloading = manager.receive_reloading().await?;
+
+    if reexecute {
+        // The manager does not reply to `Reexecute`, the call will fail
+        // once the manager disconnects from the bus to re-execute itself
+        match manager.reexecute().await {
+            Err(zbus::Error::MethodError(name, _, _))
+                if name.as_str() == "org.freedesktop.DBus.Error.NoReply" => {}
+            result => result?,
+        }
+    } else {
+        manager.reload().await?;
+    }
+
+    let finished = async {
+        while let Some(signal) = reloading.next().await {
+            if !signal.args()?.active() {
+                return Ok(());
+            }
+        }
+        Err(connection_lost(
+            "Connection lost before the manager finished reloading".to_string(),
+        ))
+    };
+
+    finished.await
+}
+
+// Convert the result of `run_job` to a JavaScript object with the job
+// path, and the job result if waiting for the job
+fn enqueued_job<'a, C: Context<'a>>(
+    cx: &mut C,
+    (job, result): (OwnedObjectPath, Option<String>),
+) -> JsResult<'a, JsObject> {
+    let obj = cx.empty_object();
+    let value = cx.string(job.as_str());
+    obj.set(cx, "path", value)?;
+    if let Some(result) = result {
+        let value = cx.string(result);
+        obj.set(cx, "result", value)?;
+    }
+
+    Ok(obj)
+}
+
+// Create a proxy to the job object at `path`
+async fn job_proxy(connection: &Connection, path: String) -> zbus::Result<JobProxy<'static>> {
+    JobProxy::builder(connection).path(path)?.build().await
+}
+
+// Read the properties of a job in the same form as the
+// jobs returned by `ListJobs`
+async fn get_job_info(connection: &Connection, path: String) -> zbus::Result<JobInfo> {
+    let job = job_proxy(connection, path).await?;
+    let (unit, unit_path) = job.unit().await?;
+
+    Ok((
+        job.id().await?,
+        unit,
+        job.job_type().await?,
+        job.state().await?,
+        job.path().to_owned().into(),
+        unit_path,
+    ))
+}
+
+// Convert a job returned by `ListJobs` to a JavaScript object
+fn job_info<'a, C: Context<'a>>(
+    cx: &mut C,
+    (id, unit, job_type, state, path, unit_path): JobInfo,
+) -> JsResult<'a, JsObject> {
+    let obj = cx.empty_object();
+    let value = cx.number(id);
+    obj.set(cx, "id", value)?;
+    let value = cx.string(unit);
+    obj.set(cx, "unit", value)?;
+    let value = cx.string(job_type);
+    obj.set(cx, "jobType", value)?;
+    let value = cx.string(state);
+    obj.set(cx, "state", value)?;
+    let value = cx.string(path.as_str());
+    obj.set(cx, "path", value)?;
+    let value = cx.string(unit_path.as_str());
+    obj.set(cx, "unitPath", value)?;
+
+    Ok(obj)
+}
+
+// Convert a list of jobs to a JavaScript array
+fn job_infos<'a, C: Context<'a>>(cx: &mut C, jobs: Vec<JobInfo>) -> JsResult<'a, JsArray> {
+    let res = cx.empty_array();
+    for (i, job) in jobs.into_iter().enumerate() {
+        let obj = job_info(cx, job)?;
+        res.set(cx, i as u32, obj)?;
+    }
+
+    Ok(res)
+}
+
+// Create a proxy to the `org.freedesktop.DBus.Properties` interface
+// of a systemd object
+async fn systemd_properties<'a>(
+    connection: &Connection,
+    path: &ObjectPath<'_>,
+) -> zbus::Result<PropertiesProxy<'a>> {
+    PropertiesProxy::builder(connection)
+        .destination("org.freedesktop.systemd1")?
+        .path(path.to_owned())?
+        .build()
+        .await
+}
+
+/// Read all properties of the given interface on the object at `path`
+/// with a single `GetAll` call
+async fn get_all_properties(
+    connection: &Connection,
+    path: &ObjectPath<'_>,
+    interface: &'static str,
+) -> zbus::Result<PropertyMap> {
+    let properties = systemd_properties(connection, path).await?;
+    let interface = InterfaceName::from_static_str(interface)?;
+
+    Ok(PropertyMap(properties.get_all(interface).await?))
+}
+
+/// Read all properties of the given interface on the object for `unit_name`
+/// with a single `GetAll` call. Fails if the unit is not loaded
+async fn get_all_unit_properties(
+    connection: &Connection,
+    unit_name: &str,
+    interface: &'static str,
+) -> zbus::Result<PropertyMap> {
+    let manager = ServiceManagerProxy::new(connection).await?;
+    let unit = manager.get_unit(unit_name).await?;
+
+    get_all_properties(connection, unit.path(), interface).await
+}
+
+/// Properties of a D-Bus object as returned by `GetAll`. Getters return
+/// `None` if the property is missing or not of the expected type
+struct PropertyMap(HashMap<String, OwnedValue>);
+
+impl PropertyMap {
+    fn get(&self, key: &str) -> Option<&Value<'static>> {
+        self.0.get(key).map(|value| &**value)
+    }
+
+    fn str(&self, key: &str) -> Option<&str> {
+        match self.get(key) {
+            Some(Value::Str(value)) => Some(value.as_str()),
+            Some(Value::ObjectPath(value)) => Some(value.as_str()),
+            _ => None,
+        }
+    }
+
+    fn strings(&self, key: &str) -> Option<Vec<String>> {
+        match self.get(key) {
+            Some(Value::Array(values)) => values
+                .get()
+                .iter()
+                .map(|value| match value {
+                    Value::Str(value) => Some(value.to_string()),
+                    _ => None,
+                })
+                .collect(),
+            _ => None,
+        }
+    }
+
+    fn bytes(&self, key: &str) -> Option<Vec<u8>> {
+        match self.get(key) {
+            Some(Value::Array(values)) => values
+                .get()
+                .iter()
+                .map(|value| match value {
+                    Value::U8(value) => Some(*value),
+                    _ => None,
+                })
+                .collect(),
+            _ => None,
+        }
+    }
+
+    fn bool(&self, key: &str) -> Option<bool> {
+        match self.get(key) {
+            Some(Value::Bool(value)) => Some(*value),
+            _ => None,
+        }
+    }
+
+    fn u64(&self, key: &str) -> Option<u64> {
+        match self.get(key) {
+            Some(Value::U64(value)) => Some(*value),
+            _ => None,
+        }
+    }
+
+    // Convert the property `key` to `T`, failing if it is
+    // missing or not of the expected type
+    fn required<T>(&self, key: &str) -> zbus::Result<T>
+    where
+        T: TryFrom<OwnedValue>,
+        T::Error: Into<zbus::Error>,
+    {
+        match self.0.get(key) {
+            Some(value) => value.clone().try_into().map_err(Into::into),
+            None => Err(zbus::Error::Failure(format!("Missing property {}", key))),
+        }
+    }
+
+    // Set `obj[js_key]` to the string property `key`, or null if missing
+    fn set_str<'a, C: Context<'a>>(
+        &self,
+        cx: &mut C,
+        obj: Handle<JsObject>,
+        js_key: &str,
+        key: &str,
+    ) -> NeonResult<()> {
+        let value = match self.str(key) {
+            Some(value) => cx.string(value).upcast(),
+            None => cx.null().upcast::<JsValue>(),
+        };
+        obj.set(cx, js_key, value)?;
+        Ok(())
+    }
+
+    // Set `obj[js_key]` to the string array property `key`, or null if missing
+    fn set_strings<'a, C: Context<'a>>(
+        &self,
+        cx: &mut C,
+        obj: Handle<JsObject>,
+        js_key: &str,
+        key: &str,
+    ) -> NeonResult<()> {
+        let value = match self.strings(key) {
+            Some(values) => {
+                let res = cx.empty_array();
+                for (i, value) in values.iter().enumerate() {
+                    let value = cx.string(value);
+                    res.set(cx, i as u32, value)?;
+                }
+                res.upcast()
+            }
+            None => cx.null().upcast::<JsValue>(),
+        };
+        obj.set(cx, js_key, value)?;
+        Ok(())
+    }
+
+    // Set `obj[js_key]` to the boolean property `key`, or null if missing
+    fn set_bool<'a, C: Context<'a>>(
+        &self,
+        cx: &mut C,
+        obj: Handle<JsObject>,
+        js_key: &str,
+        key: &str,
+    ) -> NeonResult<()> {
+        let value = match self.bool(key) {
+            Some(value) => cx.boolean(value).upcast(),
+            None => cx.null().upcast::<JsValue>(),
+        };
+        obj.set(cx, js_key, value)?;
+        Ok(())
+    }
+
+    // Set `obj[js_key]` to the resource counter property `key`. Systemd reports
+    // `u64::MAX` when accounting is disabled or the value is not available,
+    // which is set to null as well as missing properties
+    fn set_counter<'a, C: Context<'a>>(
+        &self,
+        cx: &mut C,
+        obj: Handle<JsObject>,
+        js_key: &str,
+        key: &str,
+    ) -> NeonResult<()> {
+        let value = match self.u64(key) {
+            Some(value) if value != u64::MAX => cx.number(value as f64).upcast(),
+            _ => cx.null().upcast::<JsValue>(),
+        };
+        obj.set(cx, js_key, value)?;
+        Ok(())
+    }
+
+    // Set `obj[js_key]` to the timestamp property `key` as a `Date`,
+    // or null if missing or if the event has not happened
+    fn set_timestamp<'a, C: Context<'a>>(
+        &self,
+        cx: &mut C,
+        obj: Handle<JsObject>,
+        js_key: &str,
+        key: &str,
+    ) -> NeonResult<()> {
+        let value = opt_usec_to_date(cx, self.u64(key).unwrap_or(0))?;
+        obj.set(cx, js_key, value)?;
+        Ok(())
+    }
+}
+
+/// Watches the `ActiveState` and `SubState` properties of a unit
+/// via the `PropertiesChanged` signal on the unit object
+struct UnitStateWatch {
+    subscription: ManagerSubscription,
+    changes: PropertiesChangedStream<'static>,
+    active_state: String,
+    sub_state: String,
+}
+
+impl UnitStateWatch {
+    async fn new(connection: &BusConnection, unit_name: &str) -> zbus::Result<Self> {
+        let manager = ServiceManagerProxy::new(connection).await?;
+
+        // The manager only emits `PropertiesChanged` for units to subscribed
+        // clients. The subscription is released if any of the calls below fails
+        let subscription = connection.subscribe().await?;
+        let mut unit = manager.get_unit(unit_name).await?;
+
+        // Start listening for changes before reading the initial
+        // state so no transition is lost in between
+        let properties = systemd_properties(connection, unit.path()).await?;
+        let changes = properties.receive_properties_changed().await?;
+
+        let active_state = unit.active_state().await?;
+        let sub_state = unit.sub_state().await?;
+
+        Ok(UnitStateWatch {
+            subscription,
+            changes,
+            active_state,
+            sub_state,
+        })
+    }
+
+    /// Call `on_change` with the new active and sub states every time one
+    /// of them changes, until `stop` resolves or the connection is lost.
+    ///
+    /// If stopped, the signal match rule is removed and the manager
+    /// subscription released before returning `None`. Other users of the
+    /// connection keep receiving signals until they release it as well.
+    /// If the connection is lost, the last known states are returned instead.
+    async fn run<F>(
+        self,
+        stop: &mut oneshot::Receiver<()>,
+        on_change: &F,
+    ) -> zbus::Result<Option<(String, String)>>
+    where
+        F: Fn(&str, &str),
+    {
+        let UnitStateWatch {
+            subscription,
+            changes,
+            mut active_state,
+            mut sub_state,
+        } = self;
+
+        let mut changes = changes.take_until(stop);
+        while let Some(signal) = changes.next().await {
+            let args = signal.args()?;
+            if args.interface_name().as_str() != "org.freedesktop.systemd1.Unit" {
+                continue;
+            }
+
+            let mut changed = false;
+            let properties = args.changed_properties();
+            if let Some(Value::Str(state)) = properties.get("ActiveState") {
+                changed |= state.as_str() != active_state;
+                active_state = state.to_string();
+            }
+            if let Some(Value::Str(state)) = properties.get("SubState") {
+                changed |= state.as_str() != sub_state;
+                sub_state = state.to_string();
+            }
+
+            if changed {
+                on_change(&active_state, &sub_state);
+            }
+        }
+
+        if !changes.is_stopped() {
+            return Ok(Some((active_state, sub_state)));
+        }
+
+        // Dropping the stream removes the match rule
+        drop(changes);
+        subscription.release().await.map(|_| None)
+    }
+}
+
+// Sender to stop a running watch, and receiver for the result of the teardown
+type StopHandle = (oneshot::Sender<()>, oneshot::Receiver<zbus::Result<()>>);
+
+/// A subscription to unit state changes, returned to javascript
+/// by `unitSubscribe`
+struct Subscription {
+    stop: Mutex<Option<StopHandle>>,
+}
+
+// Needed to be able to box the Subscription struct. The watch is
+// not stopped on garbage collection, `unsubscribe` needs to be
+// called explicitly
+impl Finalize for Subscription {}
+
+impl Subscription {
+    /// Stop receiving unit state changes
+    fn unsubscribe(mut cx: FunctionContext) -> JsResult<JsPromise> {
+        let rt = runtime(&mut cx)?;
+        let subscription = cx.argument::<JsBox<Subscription>>(0)?;
+        let stop = subscription.stop.lock().unwrap().take();
+        let channel = cx.channel();
+
+        let (deferred, promise) = cx.promise();
+
+        rt.spawn(async move {
+            let result = match stop {
+                Some((stop, done)) => {
+                    // The watch may have already finished
+                    // with an error if the connection was lost
+                    let _ = stop.send(());
+                    done.await.unwrap_or(Ok(()))
+                }
+                // Already unsubscribed
+                None => Ok(()),
+            };
+
+            deferred.settle_with(&channel, move |mut cx| {
+                result.or_else(|err| throw_dbus_error(&mut cx, err))?;
+                Ok(cx.undefined())
+            });
+        });
+
+        Ok(promise)
+    }
+}
+
+/// An inhibitor lock, returned to javascript by `inhibit`.
+/// The lock is held for as long as the file descriptor is open.
+struct Inhibitor {
+    fd: Mutex<Option<OwnedFd>>,
+}
+
+impl Inhibitor {
+    /// Close the file descriptor, releasing the lock. Returns
+    /// false if the lock was already released
+    fn close(&self) -> bool {
+        self.fd.lock().unwrap().take().is_some()
+    }
+
+    /// Release the inhibitor lock
+    fn release(mut cx: FunctionContext) -> JsResult<JsBoolean> {
+        let inhibitor = cx.argument::<JsBox<Inhibitor>>(0)?;
+        let released = inhibitor.close();
+        Ok(cx.boolean(released))
+    }
+}
+
+impl Finalize for Inhibitor {
+    fn finalize<'a, C: Context<'a>>(self, _: &mut C) {
+        // Make sure the lock does not outlive the javascript handle
+        self.close();
+    }
+}
+
+// Return the interface exposing the resource accounting properties
+// of a unit, which depends on the unit type
+fn cgroup_interface(unit_name: &str) -> Option<&'static str> {
+    match unit_name.rsplit_once('.').map(|(_, unit_type)| unit_type) {
+        Some("service") => Some("org.freedesktop.systemd1.Service"),
+        Some("slice") => Some("org.freedesktop.systemd1.Slice"),
+        Some("scope") => Some("org.freedesktop.systemd1.Scope"),
+        Some("socket") => Some("org.freedesktop.systemd1.Socket"),
+        Some("mount") => Some("org.freedesktop.systemd1.Mount"),
+        Some("swap") => Some("org.freedesktop.systemd1.Swap"),
+        _ => None,
+    }
+}
+
+/// Dependency properties of a unit, with the javascript name
+/// of each list
+const DEPENDENCY_PROPERTIES: &[(&str, &str)] = &[
+    ("requires", "Requires"),
+    ("requisite", "Requisite"),
+    ("wants", "Wants"),
+    ("bindsTo", "BindsTo"),
+    ("upholds", "Upholds"),
+    ("partOf", "PartOf"),
+    ("requiredBy", "RequiredBy"),
+    ("requisiteOf", "RequisiteOf"),
+    ("wantedBy", "WantedBy"),
+    ("boundBy", "BoundBy"),
+    ("upheldBy", "UpheldBy"),
+    ("consistsOf", "ConsistsOf"),
+    ("conflicts", "Conflicts"),
+    ("before", "Before"),
+    ("after", "After"),
+    ("onFailure", "OnFailure"),
+    ("triggers", "Triggers"),
+    ("triggeredBy", "TriggeredBy"),
+    ("propagatesReloadTo", "PropagatesReloadTo"),
+];
+
+/// Dependency properties followed by `dependency_tree` for each kind of
+/// dependency, the same as `systemctl list-dependencies`
+fn dependency_kind(kind: &str, reverse: bool) -> Option<&'static [&'static str]> {
+    match (kind, reverse) {
+        ("requires", false) => Some(&[
+            "Requires",
+            "Requisite",
+            "Wants",
+            "ConsistsOf",
+            "BindsTo",
+            "Upholds",
+        ]),
+        ("requires", true) => Some(&[
+            "RequiredBy",
+            "RequisiteOf",
+            "WantedBy",
+            "PartOf",
+            "BoundBy",
+            "UpheldBy",
+        ]),
+        ("before", false) | ("after", true) => Some(&["Before"]),
+        ("after", false) | ("before", true) => Some(&["After"]),
+        _ => None,
+    }
+}
+
+/// A unit in a dependency tree, children are indexes into the
+/// list of nodes returned by `dependency_tree`
+struct DependencyNode {
+    name: String,
+    children: Vec<usize>,
+}
+
+/// Walk the dependencies of `root` following the given properties, up to
+/// `depth` levels. Each level is read concurrently with one `GetAll` call
+/// per unit, and every unit is only expanded the first time it is found,
+/// so cycles do not cause an infinite walk.
+///
+/// Returns the tree nodes, with the root as the first node, and the
+/// `ActiveState` of every unit that could be read
+async fn dependency_tree(
+    connection: &Connection,
+    root: String,
+    properties: &[&str],
+    depth: u32,
+) -> zbus::Result<(Vec<DependencyNode>, HashMap<String, String>)> {
+    let mut states = HashMap::new();
+    let mut seen = HashSet::from([root.clone()]);
+    let mut nodes = vec![DependencyNode {
+        name: root,
+        children: Vec::new(),
+    }];
+
+    let mut level = vec![0];
+    let mut current = 0;
+    while !level.is_empty() {
+        let results = join_all(level.iter().map(|&i| {
+            get_all_unit_properties(connection, &nodes[i].name, "org.freedesktop.systemd1.Unit")
+        }))
+        .await;
+
+        let mut next = Vec::new();
+        for (i, result) in level.into_iter().zip(results) {
+            let unit = match result {
+                Ok(unit) => unit,
+                // The root unit needs to exist, dependencies that cannot
+                // be read are left without state
+                Err(err) if i == 0 => return Err(err),
+                Err(_) => continue,
+            };
+            if let Some(state) = unit.str("ActiveState") {
+                states.insert(nodes[i].name.clone(), state.to_string());
+            }
+            if current >= depth {
+                continue;
+            }
+
+            let mut dependencies: Vec<String> = properties
+                .iter()
+                .flat_map(|key| unit.strings(key).unwrap_or_default())
+                .collect();
+            dependencies.sort();
+            dependencies.dedup();
+
+            for name in dependencies {
+                let child = nodes.len();
+                nodes[i].children.push(child);
+                if seen.insert(name.clone()) {
+                    next.push(child);
+                }
+                nodes.push(DependencyNode {
+                    name,
+                    children: Vec::new(),
+                });
+            }
+        }
+
+        level = next;
+        current += 1;
+    }
+
+    Ok((nodes, states))
+}
+
+// Convert the node `i` of a dependency tree to a JavaScript object
+fn dependency_node<'a, C: Context<'a>>(
+    cx: &mut C,
+    nodes: &[DependencyNode],
+    states: &HashMap<String, String>,
+    i: usize,
+) -> JsResult<'a, JsObject> {
+    let node = &nodes[i];
+    let obj = cx.empty_object();
+    let value = cx.string(&node.name);
+    obj.set(cx, "name", value)?;
+    let value = match states.get(&node.name) {
+        Some(state) => cx.string(state).upcast(),
+        None => cx.null().upcast::<JsValue>(),
+    };
+    obj.set(cx, "activeState", value)?;
+
+    let children = cx.empty_array();
+    for (j, &child) in node.children.iter().enumerate() {
+        let value = dependency_node(cx, nodes, states, child)?;
+        children.set(cx, j as u32, value)?;
+    }
+    obj.set(cx, "children", children)?;
+
+    Ok(obj)
+}
+
+/// Properties of the `Service` interface of a unit
+struct ServiceStatus {
+    main_pid: u32,
+    control_pid: u32,
+    result: String,
+    exec_main_status: i32,
+    exec_main_code: i32,
+    exec_main_start_timestamp: u64,
+    n_restarts: u32,
+    status_text: String,
+    restart: String,
+    watchdog_timestamp: u64,
+    exec_start: Vec<ExecCommand>,
+}
+
+impl ServiceStatus {
+    async fn get(connection: &Connection, unit_name: &str) -> zbus::Result<Self> {
+        // Service properties are exposed on the unit object, read
+        // them all with a single `GetAll` rather than one call each
+        let properties =
+            get_all_unit_properties(connection, unit_name, "org.freedesktop.systemd1.Service")
+                .await?;
+
+        Ok(ServiceStatus {
+            main_pid: properties.required("MainPID")?,
+            control_pid: properties.required("ControlPID")?,
+            result: properties.required("Result")?,
+            exec_main_status: properties.required("ExecMainStatus")?,
+            exec_main_code: properties.required("ExecMainCode")?,
+            exec_main_start_timestamp: properties.required("ExecMainStartTimestamp")?,
+            n_restarts: properties.required("NRestarts")?,
+            status_text: properties.required("StatusText")?,
+            restart: properties.required("Restart")?,
+            watchdog_timestamp: properties.required("WatchdogTimestamp")?,
+            exec_start: properties.required("ExecStart")?,
+        })
+    }
+
+    fn to_js<'a, C: Context<'a>>(&self, cx: &mut C) -> JsResult<'a, JsObject> {
+        let obj = cx.empty_object();
+        let value = cx.number(self.main_pid);
+        obj.set(cx, "mainPid", value)?;
+        let value = cx.number(self.control_pid);
+        obj.set(cx, "controlPid", value)?;
+        let value = cx.string(&self.result);
+        obj.set(cx, "result", value)?;
+        let value = cx.number(self.exec_main_status);
+        obj.set(cx, "execMainStatus", value)?;
+        let value = cx.number(self.exec_main_code);
+        obj.set(cx, "execMainCode", value)?;
+        let value = opt_usec_to_date(cx, self.exec_main_start_timestamp)?;
+        obj.set(cx, "execMainStartTimestamp", value)?;
+        let value = cx.number(self.n_restarts);
+        obj.set(cx, "nRestarts", value)?;
+        let value = cx.string(&self.status_text);
+        obj.set(cx, "statusText", value)?;
+        let value = cx.string(&self.restart);
+        obj.set(cx, "restart", value)?;
+        let value = opt_usec_to_date(cx, self.watchdog_timestamp)?;
+        obj.set(cx, "watchdogTimestamp", value)?;
+
+        let exec_start = cx.empty_array();
+        for (i, command) in self.exec_start.iter().enumerate() {
+            let (path, argv, ignore_errors, start, _, exit, _, pid, code, status) = command;
+
+            let obj = cx.empty_object();
+            let value = cx.string(path);
+            obj.set(cx, "path", value)?;
+            let args = cx.empty_array();
+            for (j, arg) in argv.iter().enumerate() {
+                let arg = cx.string(arg);
+                args.set(cx, j as u32, arg)?;
+            }
+            obj.set(cx, "argv", args)?;
+            let value = cx.boolean(*ignore_errors);
+            obj.set(cx, "ignoreErrors", value)?;
+            let value = opt_usec_to_date(cx, *start)?;
+            obj.set(cx, "startTimestamp", value)?;
+            let value = opt_usec_to_date(cx, *exit)?;
+            obj.set(cx, "exitTimestamp", value)?;
+            let value = cx.number(*pid);
+            obj.set(cx, "pid", value)?;
+            let value = cx.number(*code);
+            obj.set(cx, "code", value)?;
+            let value = cx.number(*status);
+            obj.set(cx, "status", value)?;
+            exec_start.set(cx, i as u32, obj)?;
+        }
+        obj.set(cx, "execStart", exec_start)?;
+
+        Ok(obj)
+    }
+}
+
+// Convert a timestamp to a javascript `Date`, or to null if the
+// timestamp is 0, which systemd uses for events that have not happened
+fn opt_usec_to_date<'a, C: Context<'a>>(cx: &mut C, usec: u64) -> JsResult<'a, JsValue> {
+    if usec == 0 {
+        return Ok(cx.null().upcast());
+    }
+    Ok(usec_to_date(cx, usec)?.upcast())
+}
+
+// Convert a wall-clock timestamp in microseconds since the epoch,
+// as used by systemd, to a javascript `Date`
+fn usec_to_date<'a, C: Context<'a>>(cx: &mut C, usec: u64) -> JsResult<'a, JsDate> {
+    let ms = usec as f64 / 1000.0;
+    cx.date(ms)
+        .or_else(|err| cx.throw_range_error(err.to_string()))
+}
+
+// Convert the list of changes returned by unit file operations
+// to a JavaScript array
+fn unit_file_changes<'a, C: Context<'a>>(
+    cx: &mut C,
+    changes: Vec<UnitFileChange>,
+) -> JsResult<'a, JsArray> {
+    let res = cx.empty_array();
+    for (i, (change_type, file, destination)) in changes.into_iter().enumerate() {
+        let obj = cx.empty_object();
+        let value = cx.string(change_type);
+        obj.set(cx, "type", value)?;
+        let value = cx.string(file);
+        obj.set(cx, "file", value)?;
+        let value = cx.string(destination);
+        obj.set(cx, "destination", value)?;
+        res.set(cx, i as u32, obj)?;
+    }
+
+    Ok(res)
+}
+
+// Convert the result of unit file operations that report whether the
+// unit files have an `[Install]` section to a JavaScript object
+fn unit_file_install<'a, C: Context<'a>>(
+    cx: &mut C,
+    (carries_install_info, changes): (bool, Vec<UnitFileChange>),
+) -> JsResult<'a, JsObject> {
+    let obj = cx.empty_object();
+    let value = cx.boolean(carries_install_info);
+    obj.set(cx, "carriesInstallInfo", value)?;
+    let value = unit_file_changes(cx, changes)?;
+    obj.set(cx, "changes", value)?;
+
+    Ok(obj)
+}
+
+/// Bus a `System` is connected to, kept to be able to reconnect
+enum BusAddress {
+    System,
+    Session,
+    Address(String),
+    // Peer-to-peer connection to the systemd private socket at the given path
+    Peer(String),
+}
+
+impl BusAddress {
+    async fn connect(&self) -> zbus::Result<Connection> {
+        match self {
+            BusAddress::System => Connection::system().await,
+            BusAddress::Session => Connection::session().await,
+            BusAddress::Address(address) => {
+                ConnectionBuilder::address(address.as_str())?.build().await
+            }
+            BusAddress::Peer(path) => {
+                let stream = tokio::net::UnixStream::connect(path).await?;
+                ConnectionBuilder::unix_stream(stream).p2p().build().await
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for BusAddress {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BusAddress::System => write!(f, "D-Bus system socket"),
+            BusAddress::Session => write!(f, "D-Bus session socket"),
+            BusAddress::Address(address) => write!(f, "D-Bus address {}", address),
+            BusAddress::Peer(path) => write!(f, "systemd private socket {}", path),
+        }
+    }
+}
+
+// Javascript callback receiving connection events, with the channel to call it
+type ConnectionListener = (Channel, Arc<Root<JsFunction>>);
+
+// This is the object that will get exposed to
+// the javascript API
+struct System {
+    // The current connection, replaced every time the
+    // connection is re-established after being lost. Set
+    // to `None` once the connection is closed with `close`
+    connection: Arc<watch::Sender<Option<BusConnection>>>,
+    listener: Arc<Mutex<Option<ConnectionListener>>>,
+    // Stops monitoring the connection when closed, or
+    // when the `System` is dropped
+    monitor: Mutex<Option<oneshot::Sender<()>>>,
+}
+
+impl System {
+    fn call(&self, options: CallOptions) -> Call {
+        Call {
+            connections: self.connection.subscribe(),
+            options,
+        }
+    }
+
+    /// Close the connection. Pending calls and subscriptions are
+    /// interrupted, and the socket is released once they are dropped
+    fn close(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+        let system = cx.argument::<JsBox<System>>(0)?;
+
+        // Stop reconnecting before dropping the connection
+        if let Some(monitor) = system.monitor.lock().unwrap().take() {
+            let _ = monitor.send(());
+        }
+        system.connection.send_replace(None);
+        system.listener.lock().unwrap().take();
+
+        Ok(cx.undefined())
+    }
+
+    /// Set the callback receiving `disconnected` and `reconnected` events
+    fn set_connection_listener(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+        let system = cx.argument::<JsBox<System>>(0)?;
+        let callback = Arc::new(cx.argument::<JsFunction>(1)?.root(&mut cx));
+
+        // Connection events alone should not keep the process running
+        let mut channel = cx.channel();
+        channel.unref(&mut cx);
+
+        *system.listener.lock().unwrap() = Some((channel, callback));
+        Ok(cx.undefined())
+    }
+}
+
+// Needed to be able to box the System struct
+impl Finalize for System {}
+
+// Initial delay before trying to reconnect, doubled after every failed attempt
+const RECONNECT_MIN_DELAY: Duration = Duration::from_millis(100);
+const RECONNECT_MAX_DELAY: Duration = Duration::from_secs(30);
+
+// Run `future` to completion, unless `stop` resolves first
+async fn until_stopped<F: Future>(
+    stop: &mut oneshot::Receiver<()>,
+    future: F,
+) -> Option<F::Output> {
+    match select(stop, Box::pin(future)).await {
+        Either::Left(_) => None,
+        Either::Right((output, _)) => Some(output),
+    }
+}
+
+// Run `future` to completion, unless the connection is closed first
+async fn until_closed<F: Future>(
+    mut connections: watch::Receiver<Option<BusConnection>>,
+    future: F,
+) -> Result<F::Output, CallError> {
+    let closed = async move {
+        if connections.wait_for(Option::is_none).await.is_err() {
+            // The `System` was garbage collected, which
+            // does not interrupt running calls
+            std::future::pending::<()>().await;
+        }
+    };
+
+    match select(Box::pin(closed), Box::pin(future)).await {
+        Either::Left(_) => Err(CallError::Closed),
+        Either::Right((output, _)) => Ok(output),
+    }
+}
+
+/// A call to make on the current connection of a `System`
+struct Call {
+    connections: watch::Receiver<Option<BusConnection>>,
+    options: CallOptions,
+}
+
+impl Call {
+    /// Run the future returned by `f` with the current connection. Fails
+    /// with `CallError::Closed` if the connection is closed, or gets
+    /// closed before the future completes, and with `CallError::Aborted`
+    /// or `CallError::TimedOut` if interrupted according to the options
+    async fn run<F, Fut, T, E>(self, f: F) -> Result<T, CallError>
+    where
+        F: FnOnce(BusConnection) -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+        CallError: From<E>,
+    {
+        let connection = self.connections.borrow().clone();
+        let connection = connection.ok_or(CallError::Closed)?;
+
+        // Dropping the future on interruption cancels the call
+        let interrupted = Box::pin(self.options.interrupted());
+        let call = select(interrupted, Box::pin(f(connection)));
+        match until_closed(self.connections, call).await? {
+            Either::Left((reason, _)) => Err(reason),
+            Either::Right((result, _)) => result.map_err(CallError::from),
+        }
+    }
+}
+
+// Wait until the connection is lost, i.e. the socket is closed. Other
+// errors are reported on the stream as well, but zbus stops reading from
+// the socket after any of them, which ends the stream
+async fn disconnected(connection: Connection) {
+    let mut messages = MessageStream::from(connection);
+    while let Some(message) = messages.next().await {
+        #[allow(deprecated)]
+        if let Err(zbus::Error::InputOutput(_) | zbus::Error::Io(_)) = message {
+            break;
+        }
+    }
+}
+
+fn emit_connection_event(listener: &Mutex<Option<ConnectionListener>>, event: &'static str) {
+    if let Some((channel, callback)) = &*listener.lock().unwrap() {
+        let callback = callback.clone();
+        channel.send(move |mut cx| {
+            let callback = callback.to_inner(&mut cx);
+            let event = cx.string(event);
+            callback.call_with(&cx).arg(event).exec(&mut cx)
+        });
+    }
+}
+
+/// Wait for the connection to be lost and reconnect to `address`, with
+/// exponential backoff between attempts, until `stop` resolves. New
+/// connections are published through `connection`, so later calls and
+/// active subscriptions use them.
+async fn monitor_connection(
+    address: BusAddress,
+    connection: Arc<watch::Sender<Option<BusConnection>>>,
+    listener: Arc<Mutex<Option<ConnectionListener>>>,
+    mut stop: oneshot::Receiver<()>,
+) {
+    loop {
+        let current = match connection.borrow().clone() {
+            Some(current) => current,
+            None => return,
+        };
+        if until_stopped(&mut stop, disconnected(current.connection))
+            .await
+            .is_none()
+        {
+            return;
+        }
+        emit_connection_event(&listener, "disconnected");
+
+        let mut delay = RECONNECT_MIN_DELAY;
+        let reconnected = loop {
+            if until_stopped(&mut stop, tokio::time::sleep(delay))
+                .await
+                .is_none()
+            {
+                return;
+            }
+            match until_stopped(&mut stop, address.connect()).await {
+                Some(Ok(reconnected)) => break BusConnection::new(reconnected),
+                Some(Err(_)) => delay = (delay * 2).min(RECONNECT_MAX_DELAY),
+                None => return,
+            }
+        };
+
+        // The lost connection is closed once dropped. Do not
+        // replace the connection if it was closed meanwhile
+        let replaced = connection.send_if_modified(|current| match current {
+            Some(_) => {
+                *current = Some(reconnected);
+                true
+            }
+            None => false,
+        });
+        if !replaced {
+            return;
+        }
+        emit_connection_event(&listener, "reconnected");
+    }
+}
+
+// Connect to `address` in a background thread and resolve the returned
+// promise with the boxed `System`. Connection failures reject with
+// a `TransportError`
+fn connect_to<'a, C: Context<'a>>(cx: &mut C, address: BusAddress) -> JsResult<'a, JsPromise> {
+    let rt = runtime(cx)?;
+    let channel = cx.channel();
+    let what = address.to_string();
+    let (deferred, promise) = cx.promise();
+
+    rt.spawn(async move {
+        // Create the connection in a background thread
+        // we await the result here, but we only unwrap it inside the promise
+        // to avoid unhandle promise rejections
+        let connection = address.connect().await.map(|connection| {
+            let (connection, _) = watch::channel(Some(BusConnection::new(connection)));
+            let connection = Arc::new(connection);
+            let listener = Arc::new(Mutex::new(None));
+            let (monitor, stop) = oneshot::channel();
+
+            tokio::spawn(monitor_connection(
+                address,
+                connection.clone(),
+                listener.clone(),
+                stop,
+            ));
+
+            System {
+                connection,
+                listener,
+                monitor: Mutex::new(Some(monitor)),
+            }
+        });
+
+        deferred.settle_with(&channel, move |mut cx| {
+            let system = connection.or_else(|e| {
+                throw_transport_error(&mut cx, format!("Failed to connect to {}: {}", what, e))
+            })?;
+
+            Ok(cx.boxed(system))
+        });
+    });
+
+    Ok(promise)
+}
+
+/// Create a new connection to the system bus
+fn system(mut cx: FunctionContext) -> JsResult<JsPromise> {
+    connect_to(&mut cx, BusAddress::System)
+}
+
+/// Create a new connection to the session bus of the current user,
+/// where the user service manager lives
+fn session(mut cx: FunctionContext) -> JsResult<JsPromise> {
+    connect_to(&mut cx, BusAddress::Session)
+}
+
+/// Create a new connection to the bus at the given D-Bus address,
+/// e.g. `unix:path=/run/dbus/system_bus_socket`
+fn connect(mut cx: FunctionContext) -> JsResult<JsPromise> {
+    let address = cx.argument::<JsString>(0)?.value(&mut cx);
+    connect_to(&mut cx, BusAddress::Address(address))
+}
+
+/// Create a peer-to-peer connection to the private socket of the service
+/// manager, usually `/run/systemd/private`. This does not go through the bus
+/// daemon, so it can be used to reach PID 1 while dbus-daemon is not running.
+///
+/// There is no bus daemon on the private socket to route messages by name, but
+/// zbus requires a destination on every proxy. The proxies keep their default
+/// `org.freedesktop.systemd1` destination, which is also the destination
+/// `systemctl` sends when it talks to PID 1 over this socket as root.
+fn peer(mut cx: FunctionContext) -> JsResult<JsPromise> {
+    let path = cx.argument::<JsString>(0)?.value(&mut cx);
+    connect_to(&mut cx, BusAddress::Peer(path))
+}
+
+// Here we implement the functions that will get exposed
+// to javascript
+impl System {
+    /// Get the active state of a provided unit
+    fn unit_active_state(mut cx: FunctionContext) -> JsResult<JsPromise> {
+        let rt = runtime(&mut cx)?;
+        let system = cx.argument::<JsBox<System>>(0)?;
+        let unit_name = cx.argument::<JsString>(1)?.value(&mut cx);
+        let channel = cx.channel();
+
+        // Get a handle on the current connection to move into the spawned
+        // task. Cloning the connection is a very cheap operation and it seems
+        // that this is the way to share connections between threads
+        // https://docs.rs/zbus/3.0.0/zbus/struct.Connection.html
+        let options = call_options(&mut cx, 2)?;
+        let call = system.call(options);
+
+        // It is important to be careful not to perform failable actions after
+        // creating the promise to avoid an unhandled rejection.
+        let (deferred, promise) = cx.promise();
+
+        // Run operations on a background thread
+        rt.spawn(async move {
+            // We chain the promises with `and_then` so we can get the error
+            // to reject the promise in the
+            // settle_with block
+            let state = call
+                .run(|connection| async move {
+                    ServiceManagerProxy::new(&connection)
+                        .and_then(|manager| async move {
+                            let mut unit = manager.get_unit(&unit_name).await?;
+                            unit.active_state().await
+                        })
+                        .await
+                })
+                .await;
+
+            // Settle the promise from the result of a closure. JavaScript exceptions
+            // will be converted to a Promise rejection.
+            //
+            // This closure will execute on the JavaScript main thread. It should be
+            // limited to converting Rust types to JavaScript values. Expensive operations
+            // should be performed outside of it.
+            deferred.settle_with(&channel, move |mut cx| {
+                let state = state.or_else(|err| throw_call_error(&mut cx, err))?;
+                Ok(cx.string(state))
+            });
+        });
+
+        Ok(promise)
+    }
+
+    /// Subscribe to `ActiveState` and `SubState` changes of the unit. The
+    /// callback is called with the new states on every change
+    fn unit_subscribe(mut cx: FunctionContext) -> JsResult<JsPromise> {
+        let rt = runtime(&mut cx)?;
+        let system = cx.argument::<JsBox<System>>(0)?;
+        let unit_name = cx.argument::<JsString>(1)?.value(&mut cx);
+        let callback = Arc::new(cx.argument::<JsFunction>(2)?.root(&mut cx));
+        let channel = cx.channel();
+
+        let options = call_options(&mut cx, 3)?;
+        let call = system.call(options);
+        let mut connections = system.connection.subscribe();
+        let (stop_tx, mut stop_rx) = oneshot::channel();
+        let (done_tx, done_rx) = oneshot::channel();
+        let (deferred, promise) = cx.promise();
+
+        rt.spawn(async move {
+            // Only resolve the promise once the match rule is set-up
+            let unit = &unit_name;
+            let watch = call
+                .run(|connection| async move { UnitStateWatch::new(&connection, unit).await })
+                .await;
+            let (watch, result) = match watch {
+                Ok(watch) => (Some(watch), Ok(())),
+                Err(err) => (None, Err(err)),
+            };
+
+            deferred.settle_with(&channel, move |mut cx| {
+                result.or_else(|err| throw_call_error(&mut cx, err))?;
+                let stop = Mutex::new(Some((stop_tx, done_rx)));
+                Ok(cx.boxed(Subscription { stop }))
+            });
+
+            let mut watch = match watch {
+                Some(watch) => watch,
+                None => return,
+            };
+
+            let on_change = |active_state: &str, sub_state: &str| {
+                let callback = callback.clone();
+                let active_state = active_state.to_owned();
+                let sub_state = sub_state.to_owned();
+
+                // Call the javascript callback on the main thread
+                channel.send(move |mut cx| {
+                    let callback = callback.to_inner(&mut cx);
+                    let state = cx.empty_object();
+                    let value = cx.string(active_state);
+                    state.set(&mut cx, "activeState", value)?;
+                    let value = cx.string(sub_state);
+                    state.set(&mut cx, "subState", value)?;
+
+                    callback.call_with(&cx).arg(state).exec(&mut cx)
+                });
+            };
+
+            // The subscription ends when the connection is closed
+            let closed = connections.clone();
+            let result = until_closed(closed, async {
+                'watch: loop {
+                    let (active_state, sub_state) = match watch.run(&mut stop_rx, &on_change).await
+                    {
+                        Ok(Some(states)) => states,
+                        Ok(None) => break Ok(()),
+                        Err(err) => break Err(err),
+                    };
+
+                    // The connection was lost, watch the unit again once reconnected.
+                    // There is nothing to release on the lost connection
+                    match until_stopped(&mut stop_rx, connections.changed()).await {
+                        Some(Ok(())) => {}
+                        // Stopped, or the bus was garbage collected
+                        _ => break Ok(()),
+                    }
+
+                    // The manager may not be ready right after reconnecting
+                    let mut delay = RECONNECT_MIN_DELAY;
+                    watch = loop {
+                        let connection = match connections.borrow_and_update().clone() {
+                            Some(connection) => connection,
+                            None => break 'watch Ok(()),
+                        };
+                        if let Ok(watch) = UnitStateWatch::new(&connection, &unit_name).await {
+                            break watch;
+                        }
+
+                        if until_stopped(&mut stop_rx, tokio::time::sleep(delay))
+                            .await
+                            .is_none()
+                        {
+                            break 'watch Ok(());
+                        }
+                        delay = (delay * 2).min(RECONNECT_MAX_DELAY);
+                    };
+
+                    // Report changes that happened while disconnected
+                    if watch.active_state != active_state || watch.sub_state != sub_state {
+                        on_change(&watch.active_state, &watch.sub_state);
+                    }
+                }
+            })
+            .await
+            .unwrap_or(Ok(()));
+
+            let _ = done_tx.send(result);
+        });
+
+        Ok(promise)
+    }
+
+    /// Get a snapshot of the unit properties, the equivalent
+    /// of `systemctl show`
+    fn unit_properties(mut cx: FunctionContext) -> JsResult<JsPromise> {
+        let rt = runtime(&mut cx)?;
+        let system = cx.argument::<JsBox<System>>(0)?;
+        let unit_name = cx.argument::<JsString>(1)?.value(&mut cx);
+        let channel = cx.channel();
+
+        let options = call_options(&mut cx, 2)?;
+        let call = system.call(options);
+        let (deferred, promise) = cx.promise();
+
+        rt.spawn(async move {
+            let properties = call
+                .run(|connection| async move {
+                    // Load the unit if needed, the same as `systemctl show`,
+                    // so units that are not loaded can be inspected too
+                    let manager = ServiceManagerProxy::new(&connection).await?;
+                    let unit = manager.load_unit(&unit_name).await?;
+                    get_all_properties(&connection, unit.path(), "org.freedesktop.systemd1.Unit")
+                        .await
+                })
+                .await;
+
+            deferred.settle_with(&channel, move |mut cx| {
+                let properties = properties.or_else(|err| throw_call_error(&mut cx, err))?;
+
+                let obj = cx.empty_object();
+                properties.set_str(&mut cx, obj, "id", "Id")?;
+                properties.set_strings(&mut cx, obj, "names", "Names")?;
+                properties.set_str(&mut cx, obj, "description", "Description")?;
+                properties.set_str(&mut cx, obj, "loadState", "LoadState")?;
+                properties.set_str(&mut cx, obj, "activeState", "ActiveState")?;
+                properties.set_str(&mut cx, obj, "subState", "SubState")?;
+                properties.set_str(&mut cx, obj, "unitFileState", "UnitFileState")?;
+                properties.set_str(&mut cx, obj, "fragmentPath", "FragmentPath")?;
+                properties.set_strings(&mut cx, obj, "dropInPaths", "DropInPaths")?;
+                properties.set_bool(&mut cx, obj, "needDaemonReload", "NeedDaemonReload")?;
+
+                // The invocation id is a 128-bit id, shown as hex the same
+                // way as `systemctl show` does. An empty id means the unit
+                // has not been invoked
+                let invocation_id = match properties.bytes("InvocationID") {
+                    Some(id) if !id.is_empty() => {
+                        let id: String = id.iter().map(|b| format!("{:02x}", b)).collect();
+                        cx.string(id).upcast()
+                    }
+                    _ => cx.null().upcast::<JsValue>(),
+                };
+                obj.set(&mut cx, "invocationId", invocation_id)?;
+
+                for (js_key, key) in [
+                    ("stateChangeTimestamp", "StateChangeTimestamp"),
+                    ("inactiveExitTimestamp", "InactiveExitTimestamp"),
+                    ("activeEnterTimestamp", "ActiveEnterTimestamp"),
+                    ("activeExitTimestamp", "ActiveExitTimestamp"),
+                    ("inactiveEnterTimestamp", "InactiveEnterTimestamp"),
+                ] {
+                    properties.set_timestamp(&mut cx, obj, js_key, key)?;
+                }
+
+                Ok(obj)
+            });
+        });
+
+        Ok(promise)
+    }
+
+    /// Get all dependency lists of a unit
+    fn unit_dependencies(mut cx: FunctionContext) -> JsResult<JsPromise> {
+        let rt = runtime(&mut cx)?;
+        let system = cx.argument::<JsBox<System>>(0)?;
+        let unit_name = cx.argument::<JsString>(1)?.value(&mut cx);
+        let channel = cx.channel();
+
+        let options = call_options(&mut cx, 2)?;
+        let call = system.call(options);
+        let (deferred, promise) = cx.promise();
+
+        rt.spawn(async move {
+            let properties = call
+                .run(|connection| async move {
+                    get_all_unit_properties(
+                        &connection,
+                        &unit_name,
+                        "org.freedesktop.systemd1.Unit",
+                    )
+                    .await
+                })
+                .await;
+
+            deferred.settle_with(&channel, move |mut cx| {
+                let properties = properties.or_else(|err| throw_call_error(&mut cx, err))?;
+
+                let obj = cx.empty_object();
+                for (js_key, key) in DEPENDENCY_PROPERTIES {
+                    properties.set_strings(&mut cx, obj, js_key, key)?;
+                }
+
+                Ok(obj)
+            });
+        });
+
+        Ok(promise)
+    }
+
+    /// Get the tree of dependencies of a unit of the given kind, i.e.
+    /// `requires`, `before` or `after`, optionally limited to `depth` levels
+    fn unit_dependency_tree(mut cx: FunctionContext) -> JsResult<JsPromise> {
+        let rt = runtime(&mut cx)?;
+        let system = cx.argument::<JsBox<System>>(0)?;
+        let unit_name = cx.argument::<JsString>(1)?.value(&mut cx);
+        let kind = cx.argument::<JsString>(2)?.value(&mut cx);
+        let reverse = cx.argument::<JsBoolean>(3)?.value(&mut cx);
+        let properties = match dependency_kind(&kind, reverse) {
+            Some(properties) => properties,
+            None => return cx.throw_type_error(format!("Unknown dependency kind '{}'", kind)),
+        };
+        let depth = match cx.argument_opt(4) {
+            Some(depth) if depth.is_a::<JsNumber, _>(&mut cx) => {
+                let depth = depth
+                    .downcast_or_throw::<JsNumber, _>(&mut cx)?
+                    .value(&mut cx);
+                if depth < 0.0 {
+                    return cx.throw_range_error("depth must be a non-negative number");
+                }
+                depth.min(u32::MAX as f64) as u32
+            }
+            _ => u32::MAX,
+        };
+        let channel = cx.channel();
+
+        let options = call_options(&mut cx, 5)?;
+        let call = system.call(options);
+        let (deferred, promise) = cx.promise();
+
+        rt.spawn(async move {
+            let tree = call
+                .run(|connection| async move {
+                    dependency_tree(&connection, unit_name, properties, depth).await
+                })
+                .await;
+
+            deferred.settle_with(&channel, move |mut cx| {
+                let (nodes, states) = tree.or_else(|err| throw_call_error(&mut cx, err))?;
+                dependency_node(&mut cx, &nodes, &states, 0)
+            });
+        });
+
+        Ok(promise)
+    }
+
+    /// Get the service specific properties of a unit, e.g. the main
+    /// process id or the result of the last run
+    fn service_status(mut cx: FunctionContext) -> JsResult<JsPromise> {
+        let rt = runtime(&mut cx)?;
+        let system = cx.argument::<JsBox<System>>(0)?;
+        let unit_name = cx.argument::<JsString>(1)?.value(&mut cx);
+        let channel = cx.channel();
+
+        let options = call_options(&mut cx, 2)?;
+        let call = system.call(options);
+        let (deferred, promise) = cx.promise();
+
+        rt.spawn(async move {
+            let status = call
+                .run(|connection| async move { ServiceStatus::get(&connection, &unit_name).await })
+                .await;
+
+            deferred.settle_with(&channel, move |mut cx| {
+                let status = status.or_else(|err| throw_call_error(&mut cx, err))?;
+                status.to_js(&mut cx)
+            });
+        });
+
+        Ok(promise)
+    }
+
+    /// Get the resource usage of the unit control group
+    fn unit_resource_usage(mut cx: FunctionContext) -> JsResult<JsPromise> {
+        let rt = runtime(&mut cx)?;
+        let system = cx.argument::<JsBox<System>>(0)?;
+        let unit_name = cx.argument::<JsString>(1)?.value(&mut cx);
+        let interface = match cgroup_interface(&unit_name) {
+            Some(interface) => interface,
+            None => {
+                return cx
+                    .throw_type_error(format!("Unit {} does not have a control group", unit_name))
+            }
+        };
+        let channel = cx.channel();
+
+        let options = call_options(&mut cx, 2)?;
+        let call = system.call(options);
+        let (deferred, promise) = cx.promise();
+
+        rt.spawn(async move {
+            let properties = call
+                .run(|connection| async move {
+                    get_all_unit_properties(&connection, &unit_name, interface).await
+                })
+                .await;
+
+            deferred.settle_with(&channel, move |mut cx| {
+                let properties = properties.or_else(|err| throw_call_error(&mut cx, err))?;
+
+                let obj = cx.empty_object();
+                for (js_key, key) in [
+                    ("memoryCurrent", "MemoryCurrent"),
+                    ("memoryPeak", "MemoryPeak"),
+                    ("memorySwapCurrent", "MemorySwapCurrent"),
+                    ("cpuUsageNSec", "CPUUsageNSec"),
+                    ("tasksCurrent", "TasksCurrent"),
+                    ("ipIngressBytes", "IPIngressBytes"),
+                    ("ipEgressBytes", "IPEgressBytes"),
+                    ("ioReadBytes", "IOReadBytes"),
+                    ("ioWriteBytes", "IOWriteBytes"),
+                ] {
+                    properties.set_counter(&mut cx, obj, js_key, key)?;
+                }
+
+                Ok(obj)
+            });
+        });
+
+        Ok(promise)
+    }
+
+    fn unit_part_of(mut cx: FunctionContext) -> JsResult<JsPromise> {
+        let rt = runtime(&mut cx)?;
+        let system = cx.argument::<JsBox<System>>(0)?;
+        let unit_name = cx.argument::<JsString>(1)?.value(&mut cx);
+        let channel = cx.channel();
+
+        let options = call_options(&mut cx, 2)?;
+        let call = system.call(options);
+        let (deferred, promise) = cx.promise();
+
+        rt.spawn(async move {
+            let state = call
+                .run(|connection| async move {
+                    ServiceManagerProxy::new(&connection)
+                        .and_then(|manager| async move {
+                            let mut unit = manager.get_unit(&unit_name).await?;
+                            unit.part_of().await
+                        })
+                        .await
+                })
+                .await;
+
+            // Settle the promise from the result of a closure. JavaScript exceptions
+            // will be converted to a Promise rejection.
+            //
+            // This closure will execute on the JavaScript main thread. It should be
+            // limited to converting Rust types to JavaScript values. Expensive operations
+            // should be performed outside of it.
+            deferred.settle_with(&channel, move |mut cx| {
+                let state = state.or_else(|err| throw_call_error(&mut cx, err))?;
+
+                let res = cx.empty_array();
+                for (i, unit) in state.iter().enumerate() {
+                    let unit = cx.string(unit);
+                    res.set(&mut cx, i as u32, unit)?;
+                }
+
+                Ok(res)
+            });
+        });
+
+        Ok(promise)
+    }
+
+    /// List the units currently loaded by the manager, optionally filtered by
+    /// active state and/or unit name patterns
+    fn list_units(mut cx: FunctionContext) -> JsResult<JsPromise> {
+        let rt = runtime(&mut cx)?;
+        let system = cx.argument::<JsBox<System>>(0)?;
+        let states = string_array_arg(&mut cx, 1)?;
+        let patterns = string_array_arg(&mut cx, 2)?;
+        let channel = cx.channel();
+
+        let options = call_options(&mut cx, 3)?;
+        let call = system.call(options);
+        let (deferred, promise) = cx.promise();
+
+        rt.spawn(async move {
+            let units = call
+                .run(|connection| async move {
+                    ServiceManagerProxy::new(&connection)
+                        .and_then(|manager| async move {
+                            let states: Vec<&str> = states.iter().map(String::as_str).collect();
+                            let patterns: Vec<&str> = patterns.iter().map(String::as_str).collect();
+
+                            // Use the least specific method for the given filters, as
+                            // older systemd versions may not support the newer ones
+                            if !patterns.is_empty() {
+                                manager.list_units_by_patterns(&states, &patterns).await
+                            } else if !states.is_empty() {
+                                manager.list_units_filtered(&states).await
+                            } else {
+                                manager.list_units().await
+                            }
+                        })
+                        .await
+                })
+                .await;
+
+            deferred.settle_with(&channel, move |mut cx| {
+                let units = units.or_else(|err| throw_call_error(&mut cx, err))?;
+
+                let res = cx.empty_array();
+                for (i, unit) in units.into_iter().enumerate() {
+                    let (
+                        name,
+                        description,
+                        load_state,
+                        active_state,
+                        sub_state,
+                        followed,
+                        path,
+                        job_id,
+                        job_type,
+                        job_path,
+                    ) = unit;
+
+                    let obj = cx.empty_object();
+                    let value = cx.string(name);
+                    obj.set(&mut cx, "name", value)?;
+                    let value = cx.string(description);
+                    obj.set(&mut cx, "description", value)?;
+                    let value = cx.string(load_state);
+                    obj.set(&mut cx, "loadState", value)?;
+                    let value = cx.string(active_state);
+                    obj.set(&mut cx, "activeState", value)?;
+                    let value = cx.string(sub_state);
+                    obj.set(&mut cx, "subState", value)?;
+                    let value = cx.string(followed);
+                    obj.set(&mut cx, "followed", value)?;
+                    let value = cx.string(path.as_str());
+                    obj.set(&mut cx, "path", value)?;
+
+                    // A job id of 0 means no job is queued for the unit
+                    if job_id == 0 {
+                        let value = cx.null();
+                        obj.set(&mut cx, "job", value)?;
+                    } else {
+                        let job = cx.empty_object();
+                        let value = cx.number(job_id);
+                        job.set(&mut cx, "id", value)?;
+                        let value = cx.string(job_type);
+                        job.set(&mut cx, "type", value)?;
+                        let value = cx.string(job_path.as_str());
+                        job.set(&mut cx, "path", value)?;
+                        obj.set(&mut cx, "job", job)?;
+                    }
+
+                    res.set(&mut cx, i as u32, obj)?;
+                }
+
+                Ok(res)
+            });
+        });
+
+        Ok(promise)
+    }
+
+    /// List installed unit files and their enablement state, whether
+    /// they are loaded or not
+    fn list_unit_files(mut cx: FunctionContext) -> JsResult<JsPromise> {
+        let rt = runtime(&mut cx)?;
+        let system = cx.argument::<JsBox<System>>(0)?;
+        let states = string_array_arg(&mut cx, 1)?;
+        let patterns = string_array_arg(&mut cx, 2)?;
+        let channel = cx.channel();
+
+        let options = call_options(&mut cx, 3)?;
+        let call = system.call(options);
+        let (deferred, promise) = cx.promise();
+
+        rt.spawn(async move {
+            let files = call
+                .run(|connection| async move {
+                    ServiceManagerProxy::new(&connection)
+                        .and_then(|manager| async move {
+                            let states: Vec<&str> = states.iter().map(String::as_str).collect();
+                            let patterns: Vec<&str> = patterns.iter().map(String::as_str).collect();
+
+                            // Older systemd versions do not support filtering
+                            if states.is_empty() && patterns.is_empty() {
+                                manager.list_unit_files().await
+                            } else {
+                                manager
+                                    .list_unit_files_by_patterns(&states, &patterns)
+                                    .await
+                            }
+                        })
+                        .await
+                })
+                .await;
+
+            deferred.settle_with(&channel, move |mut cx| {
+                let files = files.or_else(|err| throw_call_error(&mut cx, err))?;
+
+                let res = cx.empty_array();
+                for (i, (path, state)) in files.into_iter().enumerate() {
+                    let obj = cx.empty_object();
+                    let value = cx.string(path);
+                    obj.set(&mut cx, "path", value)?;
+                    let value = cx.string(state);
+                    obj.set(&mut cx, "state", value)?;
+                    res.set(&mut cx, i as u32, obj)?;
+                }
+
+                Ok(res)
+            });
+        });
+
+        Ok(promise)
+    }
+
+    fn get_unit_file_state(mut cx: FunctionContext) -> JsResult<JsPromise> {
+        let rt = runtime(&mut cx)?;
+        let system = cx.argument::<JsBox<System>>(0)?;
+        let file = cx.argument::<JsString>(1)?.value(&mut cx);
+        let channel = cx.channel();
+
+        let options = call_options(&mut cx, 2)?;
+        let call = system.call(options);
+        let (deferred, promise) = cx.promise();
+
+        rt.spawn(async move {
+            let state = call
+                .run(|connection| async move {
+                    ServiceManagerProxy::new(&connection)
+                        .and_then(|manager| async move { manager.get_unit_file_state(&file).await })
+                        .await
+                })
+                .await;
+
+            deferred.settle_with(&channel, move |mut cx| {
+                let state = state.or_else(|err| throw_call_error(&mut cx, err))?;
+                Ok(cx.string(state))
+            });
+        });
+
+        Ok(promise)
+    }
+
+    /// Get the symlinks created when the unit file was enabled
+    fn get_unit_file_links(mut cx: FunctionContext) -> JsResult<JsPromise> {
+        let rt = runtime(&mut cx)?;
+        let system = cx.argument::<JsBox<System>>(0)?;
+        let name = cx.argument::<JsString>(1)?.value(&mut cx);
+        let runtime = cx.argument::<JsBoolean>(2)?.value(&mut cx);
+        let channel = cx.channel();
+
+        let options = call_options(&mut cx, 3)?;
+        let call = system.call(options);
+        let (deferred, promise) = cx.promise();
+
+        rt.spawn(async move {
+            let links = call
+                .run(|connection| async move {
+                    ServiceManagerProxy::new(&connection)
+                        .and_then(|manager| async move {
+                            manager.get_unit_file_links(&name, runtime).await
+                        })
+                        .await
+                })
+                .await;
+
+            deferred.settle_with(&channel, move |mut cx| {
+                let links = links.or_else(|err| throw_call_error(&mut cx, err))?;
+
+                let res = cx.empty_array();
+                for (i, link) in links.iter().enumerate() {
+                    let link = cx.string(link);
+                    res.set(&mut cx, i as u32, link)?;
+                }
+
+                Ok(res)
+            });
+        });
+
+        Ok(promise)
+    }
+
+    /// Enable one or more units in the system by creating symlinks
+    fn enable_unit_files(mut cx: FunctionContext) -> JsResult<JsPromise> {
+        let rt = runtime(&mut cx)?;
+        let system = cx.argument::<JsBox<System>>(0)?;
+        let files = string_array_arg(&mut cx, 1)?;
+        let runtime = cx.argument::<JsBoolean>(2)?.value(&mut cx);
+        let force = cx.argument::<JsBoolean>(3)?.value(&mut cx);
+        let channel = cx.channel();
+
+        let options = call_options(&mut cx, 4)?;
+        let call = system.call(options);
+        let (deferred, promise) = cx.promise();
+
+        rt.spawn(async move {
+            let result = call
+                .run(|connection| async move {
+                    ServiceManagerProxy::new(&connection)
+                        .and_then(|manager| async move {
+                            let files: Vec<&str> = files.iter().map(String::as_str).collect();
+                            manager.enable_unit_files(&files, runtime, force).await
+                        })
+                        .await
+                })
+                .await;
+
+            deferred.settle_with(&channel, move |mut cx| {
+                let result = result.or_else(|err| throw_call_error(&mut cx, err))?;
+                unit_file_install(&mut cx, result)
+            });
+        });
+
+        Ok(promise)
+    }
+
+    /// Disable one or more units in the system by removing symlinks
+    fn disable_unit_files(mut cx: FunctionContext) -> JsResult<JsPromise> {
+        let rt = runtime(&mut cx)?;
+        let system = cx.argument::<JsBox<System>>(0)?;
+        let files = string_array_arg(&mut cx, 1)?;
+        let runtime = cx.argument::<JsBoolean>(2)?.value(&mut cx);
+        let channel = cx.channel();
+
+        let options = call_options(&mut cx, 3)?;
+        let call = system.call(options);
+        let (deferred, promise) = cx.promise();
+
+        rt.spawn(async move {
+            let result = call
+                .run(|connection| async move {
+                    ServiceManagerProxy::new(&connection)
+                        .and_then(|manager| async move {
+                            let files: Vec<&str> = files.iter().map(String::as_str).collect();
+                            manager.disable_unit_files(&files, runtime).await
+                        })
+                        .await
+                })
+                .await;
+
+            deferred.settle_with(&channel, move |mut cx| {
+                let result = result.or_else(|err| throw_call_error(&mut cx, err))?;
+                unit_file_changes(&mut cx, result)
+            });
+        });
+
+        Ok(promise)
+    }
+
+    /// Disable and enable again one or more units in the system
+    fn reenable_unit_files(mut cx: FunctionContext) -> JsResult<JsPromise> {
+        let rt = runtime(&mut cx)?;
+        let system = cx.argument::<JsBox<System>>(0)?;
+        let files = string_array_arg(&mut cx, 1)?;
+        let runtime = cx.argument::<JsBoolean>(2)?.value(&mut cx);
+        let force = cx.argument::<JsBoolean>(3)?.value(&mut cx);
+        let channel = cx.channel();
+
+        let options = call_options(&mut cx, 4)?;
+        let call = system.call(options);
+        let (deferred, promise) = cx.promise();
+
+        rt.spawn(async move {
+            let result = call
+                .run(|connection| async move {
+                    ServiceManagerProxy::new(&connection)
+                        .and_then(|manager| async move {
+                            let files: Vec<&str> = files.iter().map(String::as_str).collect();
+                            manager.reenable_unit_files(&files, runtime, force).await
+                        })
+                        .await
+                })
+                .await;
+
+            deferred.settle_with(&channel, move |mut cx| {
+                let result = result.or_else(|err| throw_call_error(&mut cx, err))?;
+                unit_file_install(&mut cx, result)
+            });
+        });
+
+        Ok(promise)
+    }
+
+    /// Mask one or more units in the system by linking them to `/dev/null`
+    fn mask_unit_files(mut cx: FunctionContext) -> JsResult<JsPromise> {
+        let rt = runtime(&mut cx)?;
+        let system = cx.argument::<JsBox<System>>(0)?;
+        let files = string_array_arg(&mut cx, 1)?;
+        let runtime = cx.argument::<JsBoolean>(2)?.value(&mut cx);
+        let force = cx.argument::<JsBoolean>(3)?.value(&mut cx);
+        let channel = cx.channel();
+
+        let options = call_options(&mut cx, 4)?;
+        let call = system.call(options);
+        let (deferred, promise) = cx.promise();
+
+        rt.spawn(async move {
+            let result = call
+                .run(|connection| async move {
+                    ServiceManagerProxy::new(&connection)
+                        .and_then(|manager| async move {
+                            let files: Vec<&str> = files.iter().map(String::as_str).collect();
+                            manager.mask_unit_files(&files, runtime, force).await
+                        })
+                        .await
+                })
+                .await;
+
+            deferred.settle_with(&channel, move |mut cx| {
+                let result = result.or_else(|err| throw_call_error(&mut cx, err))?;
+                unit_file_changes(&mut cx, result)
+            });
+        });
+
+        Ok(promise)
+    }
+
+    /// Unmask one or more previously masked units
+    fn unmask_unit_files(mut cx: FunctionContext) -> JsResult<JsPromise> {
+        let rt = runtime(&mut cx)?;
+        let system = cx.argument::<JsBox<System>>(0)?;
+        let files = string_array_arg(&mut cx, 1)?;
+        let runtime = cx.argument::<JsBoolean>(2)?.value(&mut cx);
+        let channel = cx.channel();
+
+        let options = call_options(&mut cx, 3)?;
+        let call = system.call(options);
+        let (deferred, promise) = cx.promise();
+
+        rt.spawn(async move {
+            let result = call
+                .run(|connection| async move {
+                    ServiceManagerProxy::new(&connection)
+                        .and_then(|manager| async move {
+                            let files: Vec<&str> = files.iter().map(String::as_str).collect();
+                            manager.unmask_unit_files(&files, runtime).await
+                        })
+                        .await
+                })
+                .await;
+
+            deferred.settle_with(&channel, move |mut cx| {
+                let result = result.or_else(|err| throw_call_error(&mut cx, err))?;
+                unit_file_changes(&mut cx, result)
+            });
+        });
+
+        Ok(promise)
+    }
+
+    /// Enable or disable one or more units according to the preset policy
+    fn preset_unit_files(mut cx: FunctionContext) -> JsResult<JsPromise> {
+        let rt = runtime(&mut cx)?;
+        let system = cx.argument::<JsBox<System>>(0)?;
+        let files = string_array_arg(&mut cx, 1)?;
+        let runtime = cx.argument::<JsBoolean>(2)?.value(&mut cx);
+        let force = cx.argument::<JsBoolean>(3)?.value(&mut cx);
+        let channel = cx.channel();
+
+        let options = call_options(&mut cx, 4)?;
+        let call = system.call(options);
+        let (deferred, promise) = cx.promise();
+
+        rt.spawn(async move {
+            let result = call
+                .run(|connection| async move {
+                    ServiceManagerProxy::new(&connection)
+                        .and_then(|manager| async move {
+                            let files: Vec<&str> = files.iter().map(String::as_str).collect();
+                            manager.preset_unit_files(&files, runtime, force).await
+                        })
+                        .await
+                })
+                .await;
+
+            deferred.settle_with(&channel, move |mut cx| {
+                let result = result.or_else(|err| throw_call_error(&mut cx, err))?;
+                unit_file_install(&mut cx, result)
+            });
+        });
+
+        Ok(promise)
+    }
+
+    /// Revert one or more units to the vendor version, removing drop-ins and masks
+    fn revert_unit_files(mut cx: FunctionContext) -> JsResult<JsPromise> {
+        let rt = runtime(&mut cx)?;
+        let system = cx.argument::<JsBox<System>>(0)?;
+        let files = string_array_arg(&mut cx, 1)?;
+        let channel = cx.channel();
+
+        let options = call_options(&mut cx, 2)?;
+        let call = system.call(options);
+        let (deferred, promise) = cx.promise();
+
+        rt.spawn(async move {
+            let result = call
+                .run(|connection| async move {
+                    ServiceManagerProxy::new(&connection)
+                        .and_then(|manager| async move {
+                            let files: Vec<&str> = files.iter().map(String::as_str).collect();
+                            manager.revert_unit_files(&files).await
+                        })
+                        .await
+                })
+                .await;
+
+            deferred.settle_with(&channel, move |mut cx| {
+                let result = result.or_else(|err| throw_call_error(&mut cx, err))?;
+                unit_file_changes(&mut cx, result)
+            });
+        });
+
+        Ok(promise)
+    }
+
+    fn get_default_target(mut cx: FunctionContext) -> JsResult<JsPromise> {
+        let rt = runtime(&mut cx)?;
+        let system = cx.argument::<JsBox<System>>(0)?;
+        let channel = cx.channel();
+
+        let options = call_options(&mut cx, 1)?;
+        let call = system.call(options);
+        let (deferred, promise) = cx.promise();
+
+        rt.spawn(async move {
+            let target = call
+                .run(|connection| async move {
+                    ServiceManagerProxy::new(&connection)
+                        .and_then(|manager| async move { manager.get_default_target().await })
+                        .await
+                })
+                .await;
+
+            deferred.settle_with(&channel, move |mut cx| {
+                let target = target.or_else(|err| throw_call_error(&mut cx, err))?;
+                Ok(cx.string(target))
+            });
+        });
+
+        Ok(promise)
+    }
+
+    /// Set the target the system boots into, i.e. `systemctl set-default`
+    fn set_default_target(mut cx: FunctionContext) -> JsResult<JsPromise> {
+        let rt = runtime(&mut cx)?;
+        let system = cx.argument::<JsBox<System>>(0)?;
+        let target = cx.argument::<JsString>(1)?.value(&mut cx);
+        let force = cx.argument::<JsBoolean>(2)?.value(&mut cx);
+        let channel = cx.channel();
+
+        let options = call_options(&mut cx, 3)?;
+        let call = system.call(options);
+        let (deferred, promise) = cx.promise();
+
+        rt.spawn(async move {
+            let result = call
+                .run(|connection| async move {
+                    ServiceManagerProxy::new(&connection)
+                        .and_then(|manager| async move {
+                            manager.set_default_target(&target, force).await
+                        })
+                        .await
+                })
+                .await;
+
+            deferred.settle_with(&channel, move |mut cx| {
+                let result = result.or_else(|err| throw_call_error(&mut cx, err))?;
+                unit_file_changes(&mut cx, result)
+            });
+        });
+
+        Ok(promise)
+    }
+
+    /// Switch to the given target, stopping all units that are not its
+    /// dependencies, i.e. `systemctl isolate`
+    fn isolate(mut cx: FunctionContext) -> JsResult<JsPromise> {
+        let rt = runtime(&mut cx)?;
+        let system = cx.argument::<JsBox<System>>(0)?;
+        let target = cx.argument::<JsString>(1)?.value(&mut cx);
+        let channel = cx.channel();
+
+        let options = call_options(&mut cx, 2)?;
+        let call = system.call(options);
+        let (deferred, promise) = cx.promise();
+
+        rt.spawn(async move {
+            let result = call
+                .run(|connection| async move { isolate_target(&connection, target).await })
+                .await;
+
+            deferred.settle_with(&channel, move |mut cx| {
+                let (result, stopped) = result.or_else(|err| throw_call_error(&mut cx, err))?;
+
+                let obj = cx.empty_object();
+                let value = cx.string(result);
+                obj.set(&mut cx, "result", value)?;
+                let units = cx.empty_array();
+                for (i, unit) in stopped.iter().enumerate() {
+                    let unit = cx.string(unit);
+                    units.set(&mut cx, i as u32, unit)?;
+                }
+                obj.set(&mut cx, "stopped", units)?;
+
+                Ok(obj)
+            });
+        });
+
+        Ok(promise)
+    }
+
+    /// Reload the manager configuration, i.e. `systemctl daemon-reload`
+    fn manager_reload(mut cx: FunctionContext) -> JsResult<JsPromise> {
+        let rt = runtime(&mut cx)?;
+        let system = cx.argument::<JsBox<System>>(0)?;
+        let channel = cx.channel();
+
+        let options = call_options(&mut cx, 1)?;
+        let call = system.call(options);
+        let (deferred, promise) = cx.promise();
+
+        rt.spawn(async move {
+            let result = call
+                .run(|connection| async move { reload_manager(&connection, false).await })
+                .await;
+
+            deferred.settle_with(&channel, move |mut cx| {
+                result.or_else(|err| throw_call_error(&mut cx, err))?;
+                Ok(cx.undefined())
+            });
+        });
+
+        Ok(promise)
+    }
+
+    /// Re-execute the manager, i.e. `systemctl daemon-reexec`
+    fn manager_reexecute(mut cx: FunctionContext) -> JsResult<JsPromise> {
+        let rt = runtime(&mut cx)?;
+        let system = cx.argument::<JsBox<System>>(0)?;
+        let channel = cx.channel();
+
+        let options = call_options(&mut cx, 1)?;
+        let call = system.call(options);
+        let (deferred, promise) = cx.promise();
+
+        rt.spawn(async move {
+            let result = call
+                .run(|connection| async move { reload_manager(&connection, true).await })
+                .await;
+
+            deferred.settle_with(&channel, move |mut cx| {
+                result.or_else(|err| throw_call_error(&mut cx, err))?;
+                Ok(cx.undefined())
+            });
+        });
+
+        Ok(promise)
+    }
+
+    /// Create and start a transient unit, the equivalent of `systemd-run`
+    fn start_transient_unit(mut cx: FunctionContext) -> JsResult<JsPromise> {
+        let rt = runtime(&mut cx)?;
+        let system = cx.argument::<JsBox<System>>(0)?;
+        let unit_name = cx.argument::<JsString>(1)?.value(&mut cx);
+        let mode = cx.argument::<JsString>(2)?.value(&mut cx);
+        let properties = cx.argument::<JsObject>(3)?;
+        let properties = TransientProperties::from_js(&mut cx, properties)?.into_dbus();
+
+        // Auxiliary units are given as an array of `{ name, properties }`
+        let mut aux = Vec::new();
+        for unit in cx.argument::<JsArray>(4)?.to_vec(&mut cx)? {
+            let unit = unit.downcast_or_throw::<JsObject, _>(&mut cx)?;
+            let name = unit.get::<JsString, _, _>(&mut cx, "name")?.value(&mut cx);
+            let properties = unit.get::<JsObject, _, _>(&mut cx, "properties")?;
+            let properties = TransientProperties::from_js(&mut cx, properties)?.into_dbus();
+            aux.push((name, properties));
+        }
+
+        let wait = cx.argument::<JsBoolean>(5)?.value(&mut cx);
+        let channel = cx.channel();
+
+        let options = call_options(&mut cx, 6)?;
+        let call = system.call(options);
+        let (deferred, promise) = cx.promise();
+
+        rt.spawn(async move {
+            let result = call
+                .run(|connection| async move {
+                    run_job(&connection, wait, |manager| async move {
+                        let aux: Vec<(&str, &[(&str, Value)])> = aux
+                            .iter()
+                            .map(|(name, properties)| (name.as_str(), properties.as_slice()))
+                            .collect();
+                        manager
+                            .start_transient_unit(&unit_name, &mode, &properties, &aux)
+                            .await
+                    })
+                    .await
+                })
+                .await;
+
+            deferred.settle_with(&channel, move |mut cx| {
+                let result = result.or_else(|err| throw_call_error(&mut cx, err))?;
+                enqueued_job(&mut cx, result)
+            });
+        });
+
+        Ok(promise)
+    }
+
+    fn set_unit_properties(mut cx: FunctionContext) -> JsResult<JsPromise> {
+        let rt = runtime(&mut cx)?;
+        let system = cx.argument::<JsBox<System>>(0)?;
+        let unit_name = cx.argument::<JsString>(1)?.value(&mut cx);
+        let runtime = cx.argument::<JsBoolean>(2)?.value(&mut cx);
+        let properties = cx.argument::<JsObject>(3)?;
+        // Validate before calling systemd so nothing is applied if
+        // one of the properties is invalid
+        let properties = settable_properties_from_js(&mut cx, properties)?;
+        let channel = cx.channel();
+
+        let options = call_options(&mut cx, 4)?;
+        let call = system.call(options);
+        let (deferred, promise) = cx.promise();
+
+        rt.spawn(async move {
+            let result = call
+                .run(|connection| async move {
+                    ServiceManagerProxy::new(&connection)
+                        .and_then(|manager| async move {
+                            let properties: Vec<(&str, Value)> = properties
+                                .iter()
+                                .map(|(key, value)| (key.as_str(), value.clone()))
+                                .collect();
+                            manager
+                                .set_unit_properties(&unit_name, runtime, &properties)
+                                .await
+                        })
+                        .await
+                })
+                .await;
+
+            deferred.settle_with(&channel, move |mut cx| {
+                result.or_else(|err| throw_call_error(&mut cx, err))?;
+                Ok(cx.undefined())
+            });
+        });
+
+        Ok(promise)
+    }
+
+    fn unit_start(mut cx: FunctionContext) -> JsResult<JsPromise> {
+        let rt = runtime(&mut cx)?;
+        let system = cx.argument::<JsBox<System>>(0)?;
+        let unit_name = cx.argument::<JsString>(1)?.value(&mut cx);
+        let mode = cx.argument::<JsString>(2)?.value(&mut cx);
+        let wait = cx.argument::<JsBoolean>(3)?.value(&mut cx);
+        let channel = cx.channel();
+
+        let options = call_options(&mut cx, 4)?;
+        let call = system.call(options);
+        let (deferred, promise) = cx.promise();
+
+        // Run operations on a background thread
+        rt.spawn(async move {
+            let result = call
+                .run(|connection| async move {
+                    run_job(&connection, wait, |manager| async move {
+                        manager.start_unit(&unit_name, &mode).await
+                    })
+                    .await
+                })
+                .await;
+
+            deferred.settle_with(&channel, move |mut cx| {
+                let result = result.or_else(|err| throw_call_error(&mut cx, err))?;
+                enqueued_job(&mut cx, result)
+            });
+        });
+
+        Ok(promise)
+    }
+
+    fn unit_stop(mut cx: FunctionContext) -> JsResult<JsPromise> {
+        let rt = runtime(&mut cx)?;
+        let system = cx.argument::<JsBox<System>>(0)?;
+        let unit_name = cx.argument::<JsString>(1)?.value(&mut cx);
+        let mode = cx.argument::<JsString>(2)?.value(&mut cx);
+        let wait = cx.argument::<JsBoolean>(3)?.value(&mut cx);
+        let channel = cx.channel();
+
+        let options = call_options(&mut cx, 4)?;
+        let call = system.call(options);
+        let (deferred, promise) = cx.promise();
+
+        // Run operations on a background thread
+        rt.spawn(async move {
+            let result = call
+                .run(|connection| async move {
+                    run_job(&connection, wait, |manager| async move {
+                        manager.stop_unit(&unit_name, &mode).await
+                    })
+                    .await
+                })
+                .await;
+
+            deferred.settle_with(&channel, move |mut cx| {
+                let result = result.or_else(|err| throw_call_error(&mut cx, err))?;
+                enqueued_job(&mut cx, result)
+            });
+        });
+
+        Ok(promise)
+    }
+
+    fn unit_restart(mut cx: FunctionContext) -> JsResult<JsPromise> {
+        let rt = runtime(&mut cx)?;
+        let system = cx.argument::<JsBox<System>>(0)?;
+        let unit_name = cx.argument::<JsString>(1)?.value(&mut cx);
+        let mode = cx.argument::<JsString>(2)?.value(&mut cx);
+        let wait = cx.argument::<JsBoolean>(3)?.value(&mut cx);
+        let channel = cx.channel();
+
+        let options = call_options(&mut cx, 4)?;
+        let call = system.call(options);
+        let (deferred, promise) = cx.promise();
+
+        // Run operations on a background thread
+        rt.spawn(async move {
+            let result = call
+                .run(|connection| async move {
+                    run_job(&connection, wait, |manager| async move {
+                        manager.restart_unit(&unit_name, &mode).await
+                    })
+                    .await
+                })
+                .await;
+
+            deferred.settle_with(&channel, move |mut cx| {
+                let result = result.or_else(|err| throw_call_error(&mut cx, err))?;
+                enqueued_job(&mut cx, result)
+            });
+        });
+
+        Ok(promise)
+    }
+
+    fn unit_reload(mut cx: FunctionContext) -> JsResult<JsPromise> {
+        let rt = runtime(&mut cx)?;
+        let system = cx.argument::<JsBox<System>>(0)?;
+        let unit_name = cx.argument::<JsString>(1)?.value(&mut cx);
+        let mode = cx.argument::<JsString>(2)?.value(&mut cx);
+        let wait = cx.argument::<JsBoolean>(3)?.value(&mut cx);
+        let channel = cx.channel();
+
+        let options = call_options(&mut cx, 4)?;
+        let call = system.call(options);
+        let (deferred, promise) = cx.promise();
+
+        // Run operations on a background thread
+        rt.spawn(async move {
+            let result = call
+                .run(|connection| async move {
+                    run_job(&connection, wait, |manager| async move {
+                        manager.reload_unit(&unit_name, &mode).await
+                    })
+                    .await
+                })
+                .await;
+
+            deferred.settle_with(&channel, move |mut cx| {
+                let result = result.or_else(|err| throw_call_error(&mut cx, err))?;
+                enqueued_job(&mut cx, result)
+            });
+        });
+
+        Ok(promise)
+    }
+
+    /// Restart the unit only if it is running, a stopped unit stays stopped
+    fn unit_try_restart(mut cx: FunctionContext) -> JsResult<JsPromise> {
+        let rt = runtime(&mut cx)?;
+        let system = cx.argument::<JsBox<System>>(0)?;
+        let unit_name = cx.argument::<JsString>(1)?.value(&mut cx);
+        let mode = cx.argument::<JsString>(2)?.value(&mut cx);
+        let wait = cx.argument::<JsBoolean>(3)?.value(&mut cx);
+        let channel = cx.channel();
+
+        let options = call_options(&mut cx, 4)?;
+        let call = system.call(options);
+        let (deferred, promise) = cx.promise();
+
+        // Run operations on a background thread
+        rt.spawn(async move {
+            let result = call
+                .run(|connection| async move {
+                    run_job(&connection, wait, |manager| async move {
+                        manager.try_restart_unit(&unit_name, &mode).await
+                    })
+                    .await
+                })
+                .await;
+
+            deferred.settle_with(&channel, move |mut cx| {
+                let result = result.or_else(|err| throw_call_error(&mut cx, err))?;
+                enqueued_job(&mut cx, result)
+            });
+        });
+
+        Ok(promise)
+    }
+
+    /// Reload the unit if it supports it, restart it otherwise
+    fn unit_reload_or_restart(mut cx: FunctionContext) -> JsResult<JsPromise> {
+        let rt = runtime(&mut cx)?;
+        let system = cx.argument::<JsBox<System>>(0)?;
+        let unit_name = cx.argument::<JsString>(1)?.value(&mut cx);
+        let mode = cx.argument::<JsString>(2)?.value(&mut cx);
+        let wait = cx.argument::<JsBoolean>(3)?.value(&mut cx);
+        let channel = cx.channel();
+
+        let options = call_options(&mut cx, 4)?;
+        let call = system.call(options);
+        let (deferred, promise) = cx.promise();
+
+        // Run operations on a background thread
+        rt.spawn(async move {
+            let result = call
+                .run(|connection| async move {
+                    run_job(&connection, wait, |manager| async move {
+                        manager.reload_or_restart_unit(&unit_name, &mode).await
+                    })
+                    .await
+                })
+                .await;
+
+            deferred.settle_with(&channel, move |mut cx| {
+                let result = result.or_else(|err| throw_call_error(&mut cx, err))?;
+                enqueued_job(&mut cx, result)
+            });
+        });
+
+        Ok(promise)
+    }
+
+    /// Reload the unit if it supports it, restart it only if it is running otherwise
+    fn unit_reload_or_try_restart(mut cx: FunctionContext) -> JsResult<JsPromise> {
+        let rt = runtime(&mut cx)?;
+        let system = cx.argument::<JsBox<System>>(0)?;
+        let unit_name = cx.argument::<JsString>(1)?.value(&mut cx);
+        let mode = cx.argument::<JsString>(2)?.value(&mut cx);
+        let wait = cx.argument::<JsBoolean>(3)?.value(&mut cx);
+        let channel = cx.channel();
+
+        let options = call_options(&mut cx, 4)?;
+        let call = system.call(options);
+        let (deferred, promise) = cx.promise();
+
+        // Run operations on a background thread
+        rt.spawn(async move {
+            let result = call
+                .run(|connection| async move {
+                    run_job(&connection, wait, |manager| async move {
+                        manager.reload_or_try_restart_unit(&unit_name, &mode).await
+                    })
+                    .await
+                })
+                .await;
+
+            deferred.settle_with(&channel, move |mut cx| {
+                let result = result.or_else(|err| throw_call_error(&mut cx, err))?;
+                enqueued_job(&mut cx, result)
+            });
+        });
+
+        Ok(promise)
+    }
+
+    /// Send a signal to the processes of the unit selected by `whom`,
+    /// i.e. `main`, `control` or `all`
+    fn unit_kill(mut cx: FunctionContext) -> JsResult<JsPromise> {
+        let rt = runtime(&mut cx)?;
+        let system = cx.argument::<JsBox<System>>(0)?;
+        let unit_name = cx.argument::<JsString>(1)?.value(&mut cx);
+        let whom = cx.argument::<JsString>(2)?.value(&mut cx);
+        let signal = signal_arg(&mut cx, 3)?;
+        let channel = cx.channel();
+
+        let options = call_options(&mut cx, 4)?;
+        let call = system.call(options);
+        let (deferred, promise) = cx.promise();
+
+        rt.spawn(async move {
+            let result = call
+                .run(|connection| async move {
+                    ServiceManagerProxy::new(&connection)
+                        .and_then(|manager| async move {
+                            manager.kill_unit(&unit_name, &whom, signal).await
+                        })
+                        .await
+                })
+                .await;
+
+            deferred.settle_with(&channel, move |mut cx| {
+                result.or_else(|err| throw_call_error(&mut cx, err))?;
+                Ok(cx.undefined())
+            });
+        });
+
+        Ok(promise)
+    }
+
+    /// Queue a signal with an integer value to the processes of the unit,
+    /// the same as `kill_unit` but using sigqueue(3)
+    fn unit_queue_signal(mut cx: FunctionContext) -> JsResult<JsPromise> {
+        let rt = runtime(&mut cx)?;
+        let system = cx.argument::<JsBox<System>>(0)?;
+        let unit_name = cx.argument::<JsString>(1)?.value(&mut cx);
+        let whom = cx.argument::<JsString>(2)?.value(&mut cx);
+        let signal = signal_arg(&mut cx, 3)?;
+        let value = integer_arg::<i32>(&mut cx, 4, "signal value")?;
+        let channel = cx.channel();
+
+        let options = call_options(&mut cx, 5)?;
+        let call = system.call(options);
+        let (deferred, promise) = cx.promise();
+
+        rt.spawn(async move {
+            let result = call
+                .run(|connection| async move {
+                    ServiceManagerProxy::new(&connection)
+                        .and_then(|manager| async move {
+                            manager
+                                .queue_signal_unit(&unit_name, &whom, signal, value)
+                                .await
+                        })
+                        .await
+                })
+                .await;
+
+            deferred.settle_with(&channel, move |mut cx| {
+                result.or_else(|err| throw_call_error(&mut cx, err))?;
+                Ok(cx.undefined())
+            });
+        });
+
+        Ok(promise)
+    }
+
+    fn unit_reset_failed(mut cx: FunctionContext) -> JsResult<JsPromise> {
+        let rt = runtime(&mut cx)?;
+        let system = cx.argument::<JsBox<System>>(0)?;
+        let unit_name = cx.argument::<JsString>(1)?.value(&mut cx);
+        let channel = cx.channel();
+
+        let options = call_options(&mut cx, 2)?;
+        let call = system.call(options);
+        let (deferred, promise) = cx.promise();
+
+        rt.spawn(async move {
+            let result = call
+                .run(|connection| async move {
+                    ServiceManagerProxy::new(&connection)
+                        .and_then(
+                            |manager| async move { manager.reset_failed_unit(&unit_name).await },
+                        )
+                        .await
+                })
+                .await;
+
+            deferred.settle_with(&channel, move |mut cx| {
+                result.or_else(|err| throw_call_error(&mut cx, err))?;
+                Ok(cx.undefined())
+            });
+        });
+
+        Ok(promise)
+    }
+
+    /// Reset the failed state of all units, i.e. `systemctl reset-failed`
+    fn manager_reset_failed(mut cx: FunctionContext) -> JsResult<JsPromise> {
+        let rt = runtime(&mut cx)?;
+        let system = cx.argument::<JsBox<System>>(0)?;
+        let channel = cx.channel();
+
+        let options = call_options(&mut cx, 1)?;
+        let call = system.call(options);
+        let (deferred, promise) = cx.promise();
+
+        rt.spawn(async move {
+            let result = call
+                .run(|connection| async move {
+                    ServiceManagerProxy::new(&connection)
+                        .and_then(|manager| async move { manager.reset_failed().await })
+                        .await
+                })
+                .await;
+
+            deferred.settle_with(&channel, move |mut cx| {
+                result.or_else(|err| throw_call_error(&mut cx, err))?;
+                Ok(cx.undefined())
+            });
+        });
+
+        Ok(promise)
+    }
+
+    fn list_jobs(mut cx: FunctionContext) -> JsResult<JsPromise> {
+        let rt = runtime(&mut cx)?;
+        let system = cx.argument::<JsBox<System>>(0)?;
+        let channel = cx.channel();
+
+        let options = call_options(&mut cx, 1)?;
+        let call = system.call(options);
+        let (deferred, promise) = cx.promise();
+
+        rt.spawn(async move {
+            let jobs = call
+                .run(|connection| async move {
+                    ServiceManagerProxy::new(&connection)
+                        .and_then(|manager| async move { manager.list_jobs().await })
+                        .await
+                })
+                .await;
+
+            deferred.settle_with(&channel, move |mut cx| {
+                let jobs = jobs.or_else(|err| throw_call_error(&mut cx, err))?;
+                job_infos(&mut cx, jobs)
+            });
+        });
+
+        Ok(promise)
+    }
+
+    /// Get the object path of the job with the given id
+    fn get_job(mut cx: FunctionContext) -> JsResult<JsPromise> {
+        let rt = runtime(&mut cx)?;
+        let system = cx.argument::<JsBox<System>>(0)?;
+        let id = integer_arg::<u32>(&mut cx, 1, "job id")?;
+        let channel = cx.channel();
+
+        let options = call_options(&mut cx, 2)?;
+        let call = system.call(options);
+        let (deferred, promise) = cx.promise();
+
+        rt.spawn(async move {
+            let job = call
+                .run(|connection| async move {
+                    ServiceManagerProxy::new(&connection)
+                        .and_then(|manager| async move { manager.get_job(id).await })
+                        .await
+                        .map(|job| job.path().to_string())
+                })
+                .await;
+
+            deferred.settle_with(&channel, move |mut cx| {
+                let job = job.or_else(|err| throw_call_error(&mut cx, err))?;
+                Ok(cx.string(job))
+            });
+        });
+
+        Ok(promise)
+    }
+
+    fn cancel_job(mut cx: FunctionContext) -> JsResult<JsPromise> {
+        let rt = runtime(&mut cx)?;
+        let system = cx.argument::<JsBox<System>>(0)?;
+        let id = integer_arg::<u32>(&mut cx, 1, "job id")?;
+        let channel = cx.channel();
+
+        let options = call_options(&mut cx, 2)?;
+        let call = system.call(options);
+        let (deferred, promise) = cx.promise();
+
+        rt.spawn(async move {
+            let result = call
+                .run(|connection| async move {
+                    ServiceManagerProxy::new(&connection)
+                        .and_then(|manager| async move { manager.cancel_job(id).await })
+                        .await
+                })
+                .await;
+
+            deferred.settle_with(&channel, move |mut cx| {
+                result.or_else(|err| throw_call_error(&mut cx, err))?;
+                Ok(cx.undefined())
+            });
+        });
+
+        Ok(promise)
+    }
+
+    /// Cancel all queued jobs
+    fn clear_jobs(mut cx: FunctionContext) -> JsResult<JsPromise> {
+        let rt = runtime(&mut cx)?;
+        let system = cx.argument::<JsBox<System>>(0)?;
+        let channel = cx.channel();
+
+        let options = call_options(&mut cx, 1)?;
+        let call = system.call(options);
+        let (deferred, promise) = cx.promise();
+
+        rt.spawn(async move {
+            let result = call
+                .run(|connection| async move {
+                    ServiceManagerProxy::new(&connection)
+                        .and_then(|manager| async move { manager.clear_jobs().await })
+                        .await
+                })
+                .await;
+
+            deferred.settle_with(&channel, move |mut cx| {
+                result.or_else(|err| throw_call_error(&mut cx, err))?;
+                Ok(cx.undefined())
+            });
+        });
+
+        Ok(promise)
+    }
+
+    fn job_properties(mut cx: FunctionContext) -> JsResult<JsPromise> {
+        let rt = runtime(&mut cx)?;
+        let system = cx.argument::<JsBox<System>>(0)?;
+        let path = cx.argument::<JsString>(1)?.value(&mut cx);
+        let channel = cx.channel();
+
+        let options = call_options(&mut cx, 2)?;
+        let call = system.call(options);
+        let (deferred, promise) = cx.promise();
+
+        rt.spawn(async move {
+            let job = call
+                .run(|connection| async move { get_job_info(&connection, path).await })
+                .await;
+
+            deferred.settle_with(&channel, move |mut cx| {
+                let job = job.or_else(|err| throw_call_error(&mut cx, err))?;
+                job_info(&mut cx, job)
+            });
+        });
+
+        Ok(promise)
+    }
+
+    fn job_cancel(mut cx: FunctionContext) -> JsResult<JsPromise> {
+        let rt = runtime(&mut cx)?;
+        let system = cx.argument::<JsBox<System>>(0)?;
+        let path = cx.argument::<JsString>(1)?.value(&mut cx);
+        let channel = cx.channel();
+
+        let options = call_options(&mut cx, 2)?;
+        let call = system.call(options);
+        let (deferred, promise) = cx.promise();
+
+        rt.spawn(async move {
+            let result = call
+                .run(|connection| async move {
+                    job_proxy(&connection, path)
+                        .and_then(|job| async move { job.cancel().await })
+                        .await
+                })
+                .await;
+
+            deferred.settle_with(&channel, move |mut cx| {
+                result.or_else(|err| throw_call_error(&mut cx, err))?;
+                Ok(cx.undefined())
+            });
+        });
+
+        Ok(promise)
+    }
+
+    /// Get the jobs the given job is waiting for
+    fn job_after(mut cx: FunctionContext) -> JsResult<JsPromise> {
+        let rt = runtime(&mut cx)?;
+        let system = cx.argument::<JsBox<System>>(0)?;
+        let path = cx.argument::<JsString>(1)?.value(&mut cx);
+        let channel = cx.channel();
+
+        let options = call_options(&mut cx, 2)?;
+        let call = system.call(options);
+        let (deferred, promise) = cx.promise();
+
+        rt.spawn(async move {
+            let jobs = call
+                .run(|connection| async move {
+                    job_proxy(&connection, path)
+                        .and_then(|job| async move { job.get_after().await })
+                        .await
+                })
+                .await;
+
+            deferred.settle_with(&channel, move |mut cx| {
+                let jobs = jobs.or_else(|err| throw_call_error(&mut cx, err))?;
+                job_infos(&mut cx, jobs)
+            });
+        });
+
+        Ok(promise)
+    }
+
+    /// Get the jobs that are waiting for the given job to finish
+    fn job_before(mut cx: FunctionContext) -> JsResult<JsPromise> {
+        let rt = runtime(&mut cx)?;
+        let system = cx.argument::<JsBox<System>>(0)?;
+        let path = cx.argument::<JsString>(1)?.value(&mut cx);
+        let channel = cx.channel();
+
+        let options = call_options(&mut cx, 2)?;
+        let call = system.call(options);
+        let (deferred, promise) = cx.promise();
+
+        rt.spawn(async move {
+            let jobs = call
+                .run(|connection| async move {
+                    job_proxy(&connection, path)
+                        .and_then(|job| async move { job.get_before().await })
+                        .await
+                })
+                .await;
+
+            deferred.settle_with(&channel, move |mut cx| {
+                let jobs = jobs.or_else(|err| throw_call_error(&mut cx, err))?;
+                job_infos(&mut cx, jobs)
+            });
+        });
+
+        Ok(promise)
+    }
+
+    fn reboot(mut cx: FunctionContext) -> JsResult<JsPromise> {
+        let rt = runtime(&mut cx)?;
+        let system = cx.argument::<JsBox<System>>(0)?;
+        let interactive = cx.argument::<JsBoolean>(1)?.value(&mut cx);
+        let channel = cx.channel();
+
+        let options = call_options(&mut cx, 2)?;
+        let call = system.call(options);
+        let (deferred, promise) = cx.promise();
+
+        // Run operations on a background thread
+        rt.spawn(async move {
+            let result = call
+                .run(|connection| async move {
+                    LoginManagerProxy::new(&connection)
+                        .and_then(|manager| async move { manager.reboot(interactive).await })
+                        .await
+                })
+                .await;
+
+            deferred.settle_with(&channel, move |mut cx| {
+                result.or_else(|err| throw_call_error(&mut cx, err))?;
+                Ok(cx.undefined())
+            });
+        });
+
+        Ok(promise)
+    }
+
+    fn power_off(mut cx: FunctionContext) -> JsResult<JsPromise> {
+        let rt = runtime(&mut cx)?;
+        let system = cx.argument::<JsBox<System>>(0)?;
+        let interactive = cx.argument::<JsBoolean>(1)?.value(&mut cx);
+        let channel = cx.channel();
+
+        let options = call_options(&mut cx, 2)?;
+        let call = system.call(options);
+        let (deferred, promise) = cx.promise();
+
+        // Run operations on a background thread
+        rt.spawn(async move {
+            let result = call
+                .run(|connection| async move {
+                    LoginManagerProxy::new(&connection)
+                        .and_then(|manager| async move { manager.power_off(interactive).await })
+                        .await
+                })
+                .await;
+
+            deferred.settle_with(&channel, move |mut cx| {
+                result.or_else(|err| throw_call_error(&mut cx, err))?;
+                Ok(cx.undefined())
+            });
+        });
+
+        Ok(promise)
+    }
+
+    /// Halt the system
+    fn halt(mut cx: FunctionContext) -> JsResult<JsPromise> {
+        let rt = runtime(&mut cx)?;
+        let system = cx.argument::<JsBox<System>>(0)?;
+        let interactive = cx.argument::<JsBoolean>(1)?.value(&mut cx);
+        let channel = cx.channel();
+
+        let options = call_options(&mut cx, 2)?;
+        let call = system.call(options);
+        let (deferred, promise) = cx.promise();
+
+        // Run operations on a background thread
+        rt.spawn(async move {
+            let result = call
+                .run(|connection| async move {
+                    LoginManagerProxy::new(&connection)
+                        .and_then(|manager| async move { manager.halt(interactive).await })
+                        .await
+                })
+                .await;
+
+            deferred.settle_with(&channel, move |mut cx| {
+                result.or_else(|err| throw_call_error(&mut cx, err))?;
+                Ok(cx.undefined())
+            });
+        });
+
+        Ok(promise)
+    }
+
+    /// Suspend the system
+    fn suspend(mut cx: FunctionContext) -> JsResult<JsPromise> {
+        let rt = runtime(&mut cx)?;
+        let system = cx.argument::<JsBox<System>>(0)?;
+        let interactive = cx.argument::<JsBoolean>(1)?.value(&mut cx);
+        let channel = cx.channel();
+
+        let options = call_options(&mut cx, 2)?;
+        let call = system.call(options);
+        let (deferred, promise) = cx.promise();
+
+        // Run operations on a background thread
+        rt.spawn(async move {
+            let result = call
+                .run(|connection| async move {
+                    LoginManagerProxy::new(&connection)
+                        .and_then(|manager| async move { manager.suspend(interactive).await })
+                        .await
+                })
+                .await;
+
+            deferred.settle_with(&channel, move |mut cx| {
+                result.or_else(|err| throw_call_error(&mut cx, err))?;
+                Ok(cx.undefined())
+            });
+        });
+
+        Ok(promise)
+    }
+
+    /// Hibernate the system
+    fn hibernate(mut cx: FunctionContext) -> JsResult<JsPromise> {
+        let rt = runtime(&mut cx)?;
+        let system = cx.argument::<JsBox<System>>(0)?;
+        let interactive = cx.argument::<JsBoolean>(1)?.value(&mut cx);
+        let channel = cx.channel();
+
+        let options = call_options(&mut cx, 2)?;
+        let call = system.call(options);
+        let (deferred, promise) = cx.promise();
+
+        // Run operations on a background thread
+        rt.spawn(async move {
+            let result = call
+                .run(|connection| async move {
+                    LoginManagerProxy::new(&connection)
+                        .and_then(|manager| async move { manager.hibernate(interactive).await })
+                        .await
+                })
+                .await;
+
+            deferred.settle_with(&channel, move |mut cx| {
+                result.or_else(|err| throw_call_error(&mut cx, err))?;
+                Ok(cx.undefined())
+            });
+        });
+
+        Ok(promise)
+    }
+
+    /// Hibernate the system and suspend it
+    fn hybrid_sleep(mut cx: FunctionContext) -> JsResult<JsPromise> {
+        let rt = runtime(&mut cx)?;
+        let system = cx.argument::<JsBox<System>>(0)?;
+        let interactive = cx.argument::<JsBoolean>(1)?.value(&mut cx);
+        let channel = cx.channel();
+
+        let options = call_options(&mut cx, 2)?;
+        let call = system.call(options);
+        let (deferred, promise) = cx.promise();
 
-    #[dbus_proxy(object = "Job")]
-    fn stop_unit(&self, unit: &str, mode: &str) -> zbus::Result<Job>;
+        // Run operations on a background thread
+        rt.spawn(async move {
+            let result = call
+                .run(|connection| async move {
+                    LoginManagerProxy::new(&connection)
+                        .and_then(|manager| async move { manager.hybrid_sleep(interactive).await })
+                        .await
+                })
+                .await;
 
-    #[dbus_proxy(object = "Job")]
-    fn restart_unit(&self, unit: &str, mode: &str) -> zbus::Result<Job>;
-}
+            deferred.settle_with(&channel, move |mut cx| {
+                result.or_else(|err| throw_call_error(&mut cx, err))?;
+                Ok(cx.undefined())
+            });
+        });
 
-#[dbus_proxy(
-    default_service = "org.freedesktop.systemd1",
-    interface = "org.freedesktop.systemd1.Job"
-)]
-pub trait Job {}
+        Ok(promise)
+    }
 
-#[dbus_proxy(
-    default_service = "org.freedesktop.systemd1",
-    interface = "org.freedesktop.systemd1.Unit"
-)]
-pub trait Unit {
-    #[dbus_proxy(property)]
-    fn active_state(&mut self) -> zbus::Result<String>;
+    /// Suspend the system, hibernating it after a delay
+    fn suspend_then_hibernate(mut cx: FunctionContext) -> JsResult<JsPromise> {
+        let rt = runtime(&mut cx)?;
+        let system = cx.argument::<JsBox<System>>(0)?;
+        let interactive = cx.argument::<JsBoolean>(1)?.value(&mut cx);
+        let channel = cx.channel();
 
-    #[dbus_proxy(property)]
-    fn part_of(&mut self) -> zbus::Result<Vec<String>>;
-}
+        let options = call_options(&mut cx, 2)?;
+        let call = system.call(options);
+        let (deferred, promise) = cx.promise();
 
-#[dbus_proxy(
-    interface = "org.freedesktop.login1.Manager",
-    default_service = "org.freedesktop.login1",
-    default_path = "/org/freedesktop/login1"
-)]
-pub trait LoginManager {
-    fn reboot(&self, interactive: bool) -> zbus::Result<()>;
-    fn power_off(&self, interactive: bool) -> zbus::Result<()>;
-}
+        // Run operations on a background thread
+        rt.spawn(async move {
+            let result = call
+                .run(|connection| async move {
+                    LoginManagerProxy::new(&connection)
+                        .and_then(|manager| async move {
+                            manager.suspend_then_hibernate(interactive).await
+                        })
+                        .await
+                })
+                .await;
 
-// This is the object that will get exposed to
-// the javascript API
-struct System {
-    connection: Connection,
-}
+            deferred.settle_with(&channel, move |mut cx| {
+                result.or_else(|err| throw_call_error(&mut cx, err))?;
+                Ok(cx.undefined())
+            });
+        });
 
-// Needed to be able to box the System struct
-impl Finalize for System {}
+        Ok(promise)
+    }
 
-/// Create a new connection to the system bus
-fn system(mut cx: FunctionContext) -> JsResult<JsPromise> {
-    let rt = runtime(&mut cx)?;
-    let channel = cx.channel();
-    let (deferred, promise) = cx.promise();
+    /// Check whether the caller can reboot the system. The result is one of `yes`,
+    /// `no`, `challenge` (allowed after authentication) or `na` (not supported)
+    fn can_reboot(mut cx: FunctionContext) -> JsResult<JsPromise> {
+        let rt = runtime(&mut cx)?;
+        let system = cx.argument::<JsBox<System>>(0)?;
+        let channel = cx.channel();
 
-    rt.spawn(async move {
-        // Create the connection in a background thread
-        // we await the result here, but we only unwrap it inside the promise
-        // to avoid unhandle promise rejections
-        let connection = Connection::system().await;
-        deferred.settle_with(&channel, move |mut cx| {
-            let connection = connection.or_else(|e| {
-                cx.throw_error(format!("Failed to connect to D-Bus system socket: {}", e))
-            })?;
+        let options = call_options(&mut cx, 1)?;
+        let call = system.call(options);
+        let (deferred, promise) = cx.promise();
 
-            let system = System { connection };
+        rt.spawn(async move {
+            let result = call
+                .run(|connection| async move {
+                    LoginManagerProxy::new(&connection)
+                        .and_then(|manager| async move { manager.can_reboot().await })
+                        .await
+                })
+                .await;
 
-            Ok(cx.boxed(system))
+            deferred.settle_with(&channel, move |mut cx| {
+                let result = result.or_else(|err| throw_call_error(&mut cx, err))?;
+                Ok(cx.string(result))
+            });
         });
-    });
 
-    Ok(promise)
-}
+        Ok(promise)
+    }
 
-// Here we implement the functions that will get exposed
-// to javascript
-impl System {
-    /// Get the active state of a provided unit
-    fn unit_active_state(mut cx: FunctionContext) -> JsResult<JsPromise> {
+    /// Check whether the caller can power off the system, see `can_reboot`
+    fn can_power_off(mut cx: FunctionContext) -> JsResult<JsPromise> {
         let rt = runtime(&mut cx)?;
         let system = cx.argument::<JsBox<System>>(0)?;
-        let unit_name = cx.argument::<JsString>(1)?.value(&mut cx);
         let channel = cx.channel();
 
-        // We need to clone the connection because we are going to move it into
-        // the spawned task. Zbus documentation reports that this is a very cheap
-        // operation and it seems that this is the way to share connections
-        // between threads
-        // https://docs.rs/zbus/3.0.0/zbus/struct.Connection.html
-        let connection = system.connection.clone();
+        let options = call_options(&mut cx, 1)?;
+        let call = system.call(options);
+        let (deferred, promise) = cx.promise();
 
-        // It is important to be careful not to perform failable actions after
-        // creating the promise to avoid an unhandled rejection.
+        rt.spawn(async move {
+            let result = call
+                .run(|connection| async move {
+                    LoginManagerProxy::new(&connection)
+                        .and_then(|manager| async move { manager.can_power_off().await })
+                        .await
+                })
+                .await;
+
+            deferred.settle_with(&channel, move |mut cx| {
+                let result = result.or_else(|err| throw_call_error(&mut cx, err))?;
+                Ok(cx.string(result))
+            });
+        });
+
+        Ok(promise)
+    }
+
+    /// Check whether the caller can halt the system, see `can_reboot`
+    fn can_halt(mut cx: FunctionContext) -> JsResult<JsPromise> {
+        let rt = runtime(&mut cx)?;
+        let system = cx.argument::<JsBox<System>>(0)?;
+        let channel = cx.channel();
+
+        let options = call_options(&mut cx, 1)?;
+        let call = system.call(options);
         let (deferred, promise) = cx.promise();
 
-        // Run operations on a background thread
         rt.spawn(async move {
-            // We chain the promises with `and_then` so we can get the error
-            // to reject the promise in the
-            // settle_with block
-            let state = ServiceManagerProxy::new(&connection)
-                .and_then(|manager| async move {
-                    let mut unit = manager.get_unit(&unit_name).await?;
-                    unit.active_state().await
+            let result = call
+                .run(|connection| async move {
+                    LoginManagerProxy::new(&connection)
+                        .and_then(|manager| async move { manager.can_halt().await })
+                        .await
                 })
                 .await;
 
-            // Settle the promise from the result of a closure. JavaScript exceptions
-            // will be converted to a Promise rejection.
-            //
-            // This closure will execute on the JavaScript main thread. It should be
-            // limited to converting Rust types to JavaScript values. Expensive operations
-            // should be performed outside of it.
             deferred.settle_with(&channel, move |mut cx| {
-                // Convert a `zbus::Error` to a JavaScript exception
-                let state = state.or_else(|err| cx.throw_error(err.to_string()))?;
-                Ok(cx.string(state))
+                let result = result.or_else(|err| throw_call_error(&mut cx, err))?;
+                Ok(cx.string(result))
             });
         });
 
         Ok(promise)
     }
 
-    fn unit_part_of(mut cx: FunctionContext) -> JsResult<JsPromise> {
+    /// Check whether the caller can suspend the system, see `can_reboot`
+    fn can_suspend(mut cx: FunctionContext) -> JsResult<JsPromise> {
         let rt = runtime(&mut cx)?;
         let system = cx.argument::<JsBox<System>>(0)?;
-        let unit_name = cx.argument::<JsString>(1)?.value(&mut cx);
         let channel = cx.channel();
 
-        let connection = system.connection.clone();
+        let options = call_options(&mut cx, 1)?;
+        let call = system.call(options);
         let (deferred, promise) = cx.promise();
 
         rt.spawn(async move {
-            let state = ServiceManagerProxy::new(&connection)
-                .and_then(|manager| async move {
-                    let mut unit = manager.get_unit(&unit_name).await?;
-                    unit.part_of().await
+            let result = call
+                .run(|connection| async move {
+                    LoginManagerProxy::new(&connection)
+                        .and_then(|manager| async move { manager.can_suspend().await })
+                        .await
                 })
                 .await;
 
-            // Settle the promise from the result of a closure. JavaScript exceptions
-            // will be converted to a Promise rejection.
-            //
-            // This closure will execute on the JavaScript main thread. It should be
-            // limited to converting Rust types to JavaScript values. Expensive operations
-            // should be performed outside of it.
             deferred.settle_with(&channel, move |mut cx| {
-                // Convert a `zbus::Error` to a JavaScript exception
-                let state = state.or_else(|err| cx.throw_error(err.to_string()))?;
+                let result = result.or_else(|err| throw_call_error(&mut cx, err))?;
+                Ok(cx.string(result))
+            });
+        });
 
-                let res = cx.empty_array();
-                for (i, unit) in state.iter().enumerate() {
-                    let unit = cx.string(unit);
-                    res.set(&mut cx, i as u32, unit)?;
-                }
+        Ok(promise)
+    }
 
-                Ok(res)
+    /// Check whether the caller can hibernate the system, see `can_reboot`
+    fn can_hibernate(mut cx: FunctionContext) -> JsResult<JsPromise> {
+        let rt = runtime(&mut cx)?;
+        let system = cx.argument::<JsBox<System>>(0)?;
+        let channel = cx.channel();
+
+        let options = call_options(&mut cx, 1)?;
+        let call = system.call(options);
+        let (deferred, promise) = cx.promise();
+
+        rt.spawn(async move {
+            let result = call
+                .run(|connection| async move {
+                    LoginManagerProxy::new(&connection)
+                        .and_then(|manager| async move { manager.can_hibernate().await })
+                        .await
+                })
+                .await;
+
+            deferred.settle_with(&channel, move |mut cx| {
+                let result = result.or_else(|err| throw_call_error(&mut cx, err))?;
+                Ok(cx.string(result))
             });
         });
 
         Ok(promise)
     }
 
-    fn unit_start(mut cx: FunctionContext) -> JsResult<JsPromise> {
+    /// Check whether the caller can put the system in hybrid sleep, see `can_reboot`
+    fn can_hybrid_sleep(mut cx: FunctionContext) -> JsResult<JsPromise> {
         let rt = runtime(&mut cx)?;
         let system = cx.argument::<JsBox<System>>(0)?;
-        let unit_name = cx.argument::<JsString>(1)?.value(&mut cx);
-        let mode = cx.argument::<JsString>(2)?.value(&mut cx);
         let channel = cx.channel();
 
-        let connection = system.connection.clone();
+        let options = call_options(&mut cx, 1)?;
+        let call = system.call(options);
         let (deferred, promise) = cx.promise();
 
-        // Run operations on a background thread
         rt.spawn(async move {
-            let result = ServiceManagerProxy::new(&connection)
-                .and_then(|manager| async move { manager.start_unit(&unit_name, &mode).await })
+            let result = call
+                .run(|connection| async move {
+                    LoginManagerProxy::new(&connection)
+                        .and_then(|manager| async move { manager.can_hybrid_sleep().await })
+                        .await
+                })
                 .await;
 
             deferred.settle_with(&channel, move |mut cx| {
-                result.or_else(|err| cx.throw_error(err.to_string()))?;
-                Ok(cx.undefined())
+                let result = result.or_else(|err| throw_call_error(&mut cx, err))?;
+                Ok(cx.string(result))
             });
         });
 
         Ok(promise)
     }
 
-    fn unit_stop(mut cx: FunctionContext) -> JsResult<JsPromise> {
+    /// Check whether the caller can suspend and later hibernate the system, see `can_reboot`
+    fn can_suspend_then_hibernate(mut cx: FunctionContext) -> JsResult<JsPromise> {
         let rt = runtime(&mut cx)?;
         let system = cx.argument::<JsBox<System>>(0)?;
-        let unit_name = cx.argument::<JsString>(1)?.value(&mut cx);
-        let mode = cx.argument::<JsString>(2)?.value(&mut cx);
         let channel = cx.channel();
 
-        let connection = system.connection.clone();
+        let options = call_options(&mut cx, 1)?;
+        let call = system.call(options);
         let (deferred, promise) = cx.promise();
 
-        // Run operations on a background thread
         rt.spawn(async move {
-            let result = ServiceManagerProxy::new(&connection)
-                .and_then(|manager| async move { manager.stop_unit(&unit_name, &mode).await })
+            let result = call
+                .run(|connection| async move {
+                    LoginManagerProxy::new(&connection)
+                        .and_then(
+                            |manager| async move { manager.can_suspend_then_hibernate().await },
+                        )
+                        .await
+                })
                 .await;
 
             deferred.settle_with(&channel, move |mut cx| {
-                result.or_else(|err| cx.throw_error(err.to_string()))?;
-                Ok(cx.undefined())
+                let result = result.or_else(|err| throw_call_error(&mut cx, err))?;
+                Ok(cx.string(result))
             });
         });
 
         Ok(promise)
     }
 
-    fn unit_restart(mut cx: FunctionContext) -> JsResult<JsPromise> {
+    /// Take an inhibitor lock, blocking or delaying system shutdown
+    /// or sleep until the lock is released
+    fn inhibit(mut cx: FunctionContext) -> JsResult<JsPromise> {
         let rt = runtime(&mut cx)?;
         let system = cx.argument::<JsBox<System>>(0)?;
-        let unit_name = cx.argument::<JsString>(1)?.value(&mut cx);
-        let mode = cx.argument::<JsString>(2)?.value(&mut cx);
+        let what = cx.argument::<JsString>(1)?.value(&mut cx);
+        let who = cx.argument::<JsString>(2)?.value(&mut cx);
+        let why = cx.argument::<JsString>(3)?.value(&mut cx);
+        let mode = cx.argument::<JsString>(4)?.value(&mut cx);
         let channel = cx.channel();
 
-        let connection = system.connection.clone();
+        let options = call_options(&mut cx, 5)?;
+        let call = system.call(options);
         let (deferred, promise) = cx.promise();
 
-        // Run operations on a background thread
         rt.spawn(async move {
-            let result = ServiceManagerProxy::new(&connection)
-                .and_then(|manager| async move { manager.restart_unit(&unit_name, &mode).await })
+            let fd = call
+                .run(|connection| async move {
+                    LoginManagerProxy::new(&connection)
+                        .and_then(|manager| async move {
+                            manager.inhibit(&what, &who, &why, &mode).await
+                        })
+                        .await
+                })
                 .await;
 
             deferred.settle_with(&channel, move |mut cx| {
-                result.or_else(|err| cx.throw_error(err.to_string()))?;
-                Ok(cx.undefined())
+                let fd = fd.or_else(|err| throw_call_error(&mut cx, err))?;
+                let fd = Mutex::new(Some(fd));
+                Ok(cx.boxed(Inhibitor { fd }))
             });
         });
 
         Ok(promise)
     }
 
-    fn reboot(mut cx: FunctionContext) -> JsResult<JsPromise> {
+    /// List the currently active inhibitor locks
+    fn list_inhibitors(mut cx: FunctionContext) -> JsResult<JsPromise> {
         let rt = runtime(&mut cx)?;
         let system = cx.argument::<JsBox<System>>(0)?;
-        let interactive = cx.argument::<JsBoolean>(1)?.value(&mut cx);
         let channel = cx.channel();
 
-        let connection = system.connection.clone();
+        let options = call_options(&mut cx, 1)?;
+        let call = system.call(options);
+        let (deferred, promise) = cx.promise();
+
+        rt.spawn(async move {
+            let inhibitors = call
+                .run(|connection| async move {
+                    LoginManagerProxy::new(&connection)
+                        .and_then(|manager| async move { manager.list_inhibitors().await })
+                        .await
+                })
+                .await;
+
+            deferred.settle_with(&channel, move |mut cx| {
+                let inhibitors = inhibitors.or_else(|err| throw_call_error(&mut cx, err))?;
+
+                let res = cx.empty_array();
+                for (i, (what, who, why, mode, uid, pid)) in inhibitors.into_iter().enumerate() {
+                    let obj = cx.empty_object();
+                    let value = cx.string(what);
+                    obj.set(&mut cx, "what", value)?;
+                    let value = cx.string(who);
+                    obj.set(&mut cx, "who", value)?;
+                    let value = cx.string(why);
+                    obj.set(&mut cx, "why", value)?;
+                    let value = cx.string(mode);
+                    obj.set(&mut cx, "mode", value)?;
+                    let value = cx.number(uid);
+                    obj.set(&mut cx, "uid", value)?;
+                    let value = cx.number(pid);
+                    obj.set(&mut cx, "pid", value)?;
+                    res.set(&mut cx, i as u32, obj)?;
+                }
+
+                Ok(res)
+            });
+        });
+
+        Ok(promise)
+    }
+
+    /// Schedule a shutdown of the given type (`reboot`, `poweroff` or `halt`)
+    /// at the given wall-clock time, in milliseconds since the epoch
+    fn schedule_shutdown(mut cx: FunctionContext) -> JsResult<JsPromise> {
+        let rt = runtime(&mut cx)?;
+        let system = cx.argument::<JsBox<System>>(0)?;
+        let shutdown_type = cx.argument::<JsString>(1)?.value(&mut cx);
+        let time_ms = cx.argument::<JsNumber>(2)?.value(&mut cx);
+        if !time_ms.is_finite() || time_ms < 0.0 {
+            return cx.throw_range_error("Invalid shutdown time");
+        }
+        let usec = (time_ms * 1000.0) as u64;
+        let channel = cx.channel();
+
+        let options = call_options(&mut cx, 3)?;
+        let call = system.call(options);
         let (deferred, promise) = cx.promise();
 
-        // Run operations on a background thread
         rt.spawn(async move {
-            let result = LoginManagerProxy::new(&connection)
-                .and_then(|manager| async move { manager.reboot(interactive).await })
+            let result = call
+                .run(|connection| async move {
+                    LoginManagerProxy::new(&connection)
+                        .and_then(|manager| async move {
+                            manager.schedule_shutdown(&shutdown_type, usec).await
+                        })
+                        .await
+                })
                 .await;
 
             deferred.settle_with(&channel, move |mut cx| {
-                result.or_else(|err| cx.throw_error(err.to_string()))?;
+                result.or_else(|err| throw_call_error(&mut cx, err))?;
                 Ok(cx.undefined())
             });
         });
@@ -282,40 +4444,263 @@ impl System {
         Ok(promise)
     }
 
-    fn power_off(mut cx: FunctionContext) -> JsResult<JsPromise> {
+    /// Cancel a scheduled shutdown, resolving to false
+    /// if no shutdown was scheduled
+    fn cancel_scheduled_shutdown(mut cx: FunctionContext) -> JsResult<JsPromise> {
         let rt = runtime(&mut cx)?;
         let system = cx.argument::<JsBox<System>>(0)?;
-        let interactive = cx.argument::<JsBoolean>(1)?.value(&mut cx);
         let channel = cx.channel();
 
-        let connection = system.connection.clone();
+        let options = call_options(&mut cx, 1)?;
+        let call = system.call(options);
+        let (deferred, promise) = cx.promise();
+
+        rt.spawn(async move {
+            let result = call
+                .run(|connection| async move {
+                    LoginManagerProxy::new(&connection)
+                        .and_then(
+                            |manager| async move { manager.cancel_scheduled_shutdown().await },
+                        )
+                        .await
+                })
+                .await;
+
+            deferred.settle_with(&channel, move |mut cx| {
+                let cancelled = result.or_else(|err| throw_call_error(&mut cx, err))?;
+                Ok(cx.boolean(cancelled))
+            });
+        });
+
+        Ok(promise)
+    }
+
+    /// Set the message sent to logged in users before a shutdown
+    fn set_wall_message(mut cx: FunctionContext) -> JsResult<JsPromise> {
+        let rt = runtime(&mut cx)?;
+        let system = cx.argument::<JsBox<System>>(0)?;
+        let message = cx.argument::<JsString>(1)?.value(&mut cx);
+        let enable = cx.argument::<JsBoolean>(2)?.value(&mut cx);
+        let channel = cx.channel();
+
+        let options = call_options(&mut cx, 3)?;
+        let call = system.call(options);
         let (deferred, promise) = cx.promise();
 
-        // Run operations on a background thread
         rt.spawn(async move {
-            let result = LoginManagerProxy::new(&connection)
-                .and_then(|manager| async move { manager.power_off(interactive).await })
+            let result = call
+                .run(|connection| async move {
+                    LoginManagerProxy::new(&connection)
+                        .and_then(|manager| async move {
+                            manager.set_wall_message(&message, enable).await
+                        })
+                        .await
+                })
                 .await;
 
             deferred.settle_with(&channel, move |mut cx| {
-                result.or_else(|err| cx.throw_error(err.to_string()))?;
+                result.or_else(|err| throw_call_error(&mut cx, err))?;
                 Ok(cx.undefined())
             });
         });
 
         Ok(promise)
     }
+
+    /// Get the currently scheduled shutdown as `{ type, time }`,
+    /// or null if no shutdown is scheduled
+    fn scheduled_shutdown(mut cx: FunctionContext) -> JsResult<JsPromise> {
+        let rt = runtime(&mut cx)?;
+        let system = cx.argument::<JsBox<System>>(0)?;
+        let channel = cx.channel();
+
+        let options = call_options(&mut cx, 1)?;
+        let call = system.call(options);
+        let (deferred, promise) = cx.promise();
+
+        rt.spawn(async move {
+            let result = call
+                .run(|connection| async move {
+                    LoginManagerProxy::new(&connection)
+                        .and_then(|manager| async move { manager.scheduled_shutdown().await })
+                        .await
+                })
+                .await;
+
+            deferred.settle_with(&channel, move |mut cx| {
+                let (shutdown_type, usec) = result.or_else(|err| throw_call_error(&mut cx, err))?;
+
+                // An empty type means no shutdown is scheduled
+                if shutdown_type.is_empty() {
+                    return Ok(cx.null().upcast::<JsValue>());
+                }
+
+                let obj = cx.empty_object();
+                let value = cx.string(shutdown_type);
+                obj.set(&mut cx, "type", value)?;
+                let value = usec_to_date(&mut cx, usec)?;
+                obj.set(&mut cx, "time", value)?;
+
+                Ok(obj.upcast())
+            });
+        });
+
+        Ok(promise)
+    }
 }
 
 #[neon::main]
 fn main(mut cx: ModuleContext) -> NeonResult<()> {
+    cx.export_function("setErrorClasses", set_error_classes)?;
     cx.export_function("system", system)?;
+    cx.export_function("session", session)?;
+    cx.export_function("connect", connect)?;
+    cx.export_function("peer", peer)?;
+    cx.export_function("setConnectionListener", System::set_connection_listener)?;
+    cx.export_function("close", System::close)?;
+    cx.export_function("abortHandle", AbortHandle::create)?;
+    cx.export_function("abort", AbortHandle::abort)?;
     cx.export_function("unitActiveState", System::unit_active_state)?;
     cx.export_function("unitPartOf", System::unit_part_of)?;
+    cx.export_function("unitDependencies", System::unit_dependencies)?;
+    cx.export_function("unitDependencyTree", System::unit_dependency_tree)?;
+    cx.export_function("unitProperties", System::unit_properties)?;
+    cx.export_function("serviceStatus", System::service_status)?;
+    cx.export_function("unitResourceUsage", System::unit_resource_usage)?;
+    cx.export_function("unitSubscribe", System::unit_subscribe)?;
+    cx.export_function("unsubscribe", Subscription::unsubscribe)?;
+    cx.export_function("listUnits", System::list_units)?;
+    cx.export_function("listUnitFiles", System::list_unit_files)?;
+    cx.export_function("getUnitFileState", System::get_unit_file_state)?;
+    cx.export_function("getUnitFileLinks", System::get_unit_file_links)?;
+    cx.export_function("enableUnitFiles", System::enable_unit_files)?;
+    cx.export_function("disableUnitFiles", System::disable_unit_files)?;
+    cx.export_function("reenableUnitFiles", System::reenable_unit_files)?;
+    cx.export_function("maskUnitFiles", System::mask_unit_files)?;
+    cx.export_function("unmaskUnitFiles", System::unmask_unit_files)?;
+    cx.export_function("presetUnitFiles", System::preset_unit_files)?;
+    cx.export_function("revertUnitFiles", System::revert_unit_files)?;
+    cx.export_function("getDefaultTarget", System::get_default_target)?;
+    cx.export_function("setDefaultTarget", System::set_default_target)?;
+    cx.export_function("isolate", System::isolate)?;
+    cx.export_function("managerReload", System::manager_reload)?;
+    cx.export_function("managerReexecute", System::manager_reexecute)?;
+    cx.export_function("startTransientUnit", System::start_transient_unit)?;
+    cx.export_function("setUnitProperties", System::set_unit_properties)?;
     cx.export_function("unitStart", System::unit_start)?;
     cx.export_function("unitStop", System::unit_stop)?;
     cx.export_function("unitRestart", System::unit_restart)?;
+    cx.export_function("unitReload", System::unit_reload)?;
+    cx.export_function("unitTryRestart", System::unit_try_restart)?;
+    cx.export_function("unitReloadOrRestart", System::unit_reload_or_restart)?;
+    cx.export_function("unitReloadOrTryRestart", System::unit_reload_or_try_restart)?;
+    cx.export_function("unitKill", System::unit_kill)?;
+    cx.export_function("unitQueueSignal", System::unit_queue_signal)?;
+    cx.export_function("unitResetFailed", System::unit_reset_failed)?;
+    cx.export_function("managerResetFailed", System::manager_reset_failed)?;
+    cx.export_function("listJobs", System::list_jobs)?;
+    cx.export_function("getJob", System::get_job)?;
+    cx.export_function("cancelJob", System::cancel_job)?;
+    cx.export_function("clearJobs", System::clear_jobs)?;
+    cx.export_function("jobProperties", System::job_properties)?;
+    cx.export_function("jobCancel", System::job_cancel)?;
+    cx.export_function("jobAfter", System::job_after)?;
+    cx.export_function("jobBefore", System::job_before)?;
     cx.export_function("reboot", System::reboot)?;
     cx.export_function("powerOff", System::power_off)?;
+    cx.export_function("halt", System::halt)?;
+    cx.export_function("suspend", System::suspend)?;
+    cx.export_function("hibernate", System::hibernate)?;
+    cx.export_function("hybridSleep", System::hybrid_sleep)?;
+    cx.export_function("suspendThenHibernate", System::suspend_then_hibernate)?;
+    cx.export_function("inhibit", System::inhibit)?;
+    cx.export_function("inhibitorRelease", Inhibitor::release)?;
+    cx.export_function("listInhibitors", System::list_inhibitors)?;
+    cx.export_function("scheduleShutdown", System::schedule_shutdown)?;
+    cx.export_function("cancelScheduledShutdown", System::cancel_scheduled_shutdown)?;
+    cx.export_function("setWallMessage", System::set_wall_message)?;
+    cx.export_function("scheduledShutdown", System::scheduled_shutdown)?;
+    cx.export_function("canReboot", System::can_reboot)?;
+    cx.export_function("canPowerOff", System::can_power_off)?;
+    cx.export_function("canHalt", System::can_halt)?;
+    cx.export_function("canSuspend", System::can_suspend)?;
+    cx.export_function("canHibernate", System::can_hibernate)?;
+    cx.export_function("canHybridSleep", System::can_hybrid_sleep)?;
+    cx.export_function(
+        "canSuspendThenHibernate",
+        System::can_suspend_then_hibernate,
+    )?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn parse_signal_by_name() {
+        assert_eq!(parse_signal("SIGTERM"), Some(15));
+        assert_eq!(parse_signal("TERM"), Some(15));
+        assert_eq!(parse_signal("SIGKILL"), Some(9));
+        assert_eq!(parse_signal("SIGFOO"), None);
+        assert_eq!(parse_signal("sigterm"), None);
+    }
+
+    #[test]
+    fn parse_signal_real_time() {
+        assert_eq!(parse_signal("SIGRTMIN"), Some(SIGRTMIN));
+        assert_eq!(parse_signal("RTMAX"), Some(SIGRTMAX));
+        assert_eq!(parse_signal("SIGRTMIN+2"), Some(SIGRTMIN + 2));
+        assert_eq!(parse_signal("RTMAX-1"), Some(SIGRTMAX - 1));
+        assert_eq!(parse_signal("RTMIN+31"), None);
+        assert_eq!(parse_signal("RTMAX-31"), None);
+        assert_eq!(parse_signal("RTMIN+x"), None);
+    }
+
+    #[test]
+    fn dependency_kind_reverse() {
+        assert_eq!(dependency_kind("before", false), Some(&["Before"][..]));
+        assert_eq!(dependency_kind("after", true), Some(&["Before"][..]));
+        assert_eq!(dependency_kind("after", false), Some(&["After"][..]));
+        assert_eq!(dependency_kind("before", true), Some(&["After"][..]));
+
+        let requires = dependency_kind("requires", false).unwrap();
+        assert!(requires.contains(&"Wants"));
+        let required_by = dependency_kind("requires", true).unwrap();
+        assert!(required_by.contains(&"WantedBy"));
+
+        assert_eq!(dependency_kind("wants", false), None);
+    }
+
+    #[test]
+    fn transient_properties_into_dbus() {
+        let properties = TransientProperties {
+            exec_start: Some(vec!["/bin/sleep".to_string(), "10".to_string()]),
+            memory_max: Some(1024),
+            cpu_quota: Some(20.0),
+            runtime_max_sec: Some(1.5),
+            ..Default::default()
+        };
+        let properties: HashMap<_, _> = properties.into_dbus().into_iter().collect();
+
+        assert_eq!(properties.len(), 4);
+        assert_eq!(
+            properties["ExecStart"],
+            Value::from(vec![(
+                "/bin/sleep".to_string(),
+                vec!["/bin/sleep".to_string(), "10".to_string()],
+                false
+            )])
+        );
+        assert_eq!(properties["MemoryMax"], Value::U64(1024));
+        // 20% of one CPU is 200ms of CPU time per second
+        assert_eq!(properties["CPUQuotaPerSecUSec"], Value::U64(200_000));
+        assert_eq!(properties["RuntimeMaxUSec"], Value::U64(1_500_000));
+    }
+
+    #[test]
+    fn transient_properties_empty() {
+        assert!(TransientProperties::default().into_dbus().is_empty());
+    }
+}
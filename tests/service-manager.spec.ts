@@ -1,5 +1,27 @@
+import { setTimeout } from 'timers/promises';
 import { expect } from './chai';
-import { singleton, ServiceManager } from '../lib';
+import {
+	singleton,
+	system,
+	connect,
+	peer,
+	close,
+	ServiceManager,
+	MethodError,
+	TransportError,
+	AbortError,
+	TimeoutError,
+	DependencyKind,
+	SettableUnitProperties,
+} from '../lib';
+
+// Wait for callbacks delivered asynchronously from the native module
+async function eventually(condition: () => boolean, timeoutMs = 5000) {
+	const start = Date.now();
+	while (!condition() && Date.now() - start < timeoutMs) {
+		await setTimeout(50);
+	}
+}
 
 describe('ServiceManager', () => {
 	describe('Unit', () => {
@@ -31,6 +53,77 @@ describe('ServiceManager', () => {
 			).to.eventually.equal('active');
 		});
 
+		it('activeState rejects with a MethodError for unknown units', async () => {
+			const bus = await singleton();
+			const manager = new ServiceManager(bus);
+			await expect(
+				manager.getUnit('unknown.service').activeState,
+			).to.be.rejectedWith(MethodError);
+		});
+
+		it('activeState rejects with an AbortError when aborted', async () => {
+			const bus = await singleton();
+			const manager = new ServiceManager(bus);
+			const controller = new AbortController();
+			controller.abort();
+			await expect(
+				manager
+					.getUnit('dummy.service')
+					.getActiveState({ signal: controller.signal }),
+			).to.be.rejectedWith(AbortError);
+		});
+
+		it('rejects with an AbortError when aborted while pending', async () => {
+			const bus = await singleton();
+			const unit = new ServiceManager(bus).getUnit('dummy.service');
+			const controller = new AbortController();
+
+			// The native call is made before `restart` returns, so this
+			// aborts it while waiting for the job to finish
+			const job = unit.restart('fail', {
+				wait: true,
+				signal: controller.signal,
+			});
+			controller.abort();
+			await expect(job).to.be.rejectedWith(AbortError);
+
+			// Aborting releases the manager subscription of the call
+			await expect(unit.restart('fail', { wait: true })).to.not.be.rejected;
+		});
+
+		it('activeState rejects with a TimeoutError when timing out', async () => {
+			const bus = await singleton();
+			const manager = new ServiceManager(bus);
+			await expect(
+				manager.getUnit('dummy.service').getActiveState({ timeoutMs: 0 }),
+			).to.be.rejectedWith(TimeoutError);
+		});
+
+		it('properties can be queried', async () => {
+			const bus = await singleton();
+			const manager = new ServiceManager(bus);
+			const properties = await manager.getUnit('dummy.service').properties;
+			expect(properties.activeState).to.equal(
+				await manager.getUnit('dummy.service').activeState,
+			);
+		});
+
+		it('properties can be queried for units that are not loaded', async () => {
+			const bus = await singleton();
+			const manager = new ServiceManager(bus);
+			const properties = await manager.getUnit('unknown.service').properties;
+			expect(properties.loadState).to.equal('not-found');
+			expect(properties.activeState).to.equal('inactive');
+		});
+
+		it('serviceStatus can be queried', async () => {
+			const bus = await singleton();
+			const manager = new ServiceManager(bus);
+			const status = await manager.getUnit('dummy.service').serviceStatus;
+			expect(status.result).to.be.a('string');
+			expect(status.execStart).to.be.an('array');
+		});
+
 		it('partOf can be queried', async () => {
 			const bus = await singleton();
 			const manager = new ServiceManager(bus);
@@ -38,6 +131,66 @@ describe('ServiceManager', () => {
 				manager.getUnit('dummy.service').partOf,
 			).to.eventually.deep.equal([]);
 		});
+
+		it('dependencies can be queried', async () => {
+			const bus = await singleton();
+			const manager = new ServiceManager(bus);
+			const dependencies = await manager.getUnit('dummy.service').dependencies;
+			expect(dependencies.partOf).to.deep.equal([]);
+		});
+
+		it('dependencyTree starts at the unit', async () => {
+			const bus = await singleton();
+			const unit = new ServiceManager(bus).getUnit('dummy.service');
+
+			const tree = await unit.dependencyTree();
+			expect(tree.name).to.equal('dummy.service');
+			expect(tree.activeState).to.equal(await unit.activeState);
+
+			const root = await unit.dependencyTree({ depth: 0 });
+			expect(root.children).to.deep.equal([]);
+		});
+
+		it('dependencyTree rejects unknown kinds and negative depths', async () => {
+			const bus = await singleton();
+			const unit = new ServiceManager(bus).getUnit('dummy.service');
+
+			await expect(
+				unit.dependencyTree({ kind: 'wants' as string as DependencyKind }),
+			).to.be.rejectedWith(TypeError);
+			await expect(unit.dependencyTree({ depth: -1 })).to.be.rejectedWith(
+				RangeError,
+			);
+		});
+
+		it('setProperties rejects unknown properties and invalid values', async () => {
+			const bus = await singleton();
+			const unit = new ServiceManager(bus).getUnit('dummy.service');
+
+			await expect(
+				unit.setProperties({
+					properties: {
+						Description: 'dummy',
+					} as unknown as SettableUnitProperties,
+				}),
+			).to.be.rejectedWith(TypeError);
+			for (const value of [-1, 1.5, NaN]) {
+				await expect(
+					unit.setProperties({ properties: { MemoryMax: value } }),
+				).to.be.rejectedWith(RangeError);
+			}
+			// Infinity removes the limit, but only for limits
+			await expect(
+				unit.setProperties({ properties: { CPUWeight: Infinity } }),
+			).to.be.rejectedWith(RangeError);
+			await expect(
+				unit.setProperties({
+					properties: {
+						CPUWeight: '100',
+					} as unknown as SettableUnitProperties,
+				}),
+			).to.be.rejectedWith(TypeError);
+		});
 	});
 
 	describe('ServiceManager', () => {
@@ -68,5 +221,192 @@ describe('ServiceManager', () => {
 			await expect(unit.restart('fail')).to.not.be.rejected;
 			await expect(unit.activeState).to.eventually.equal('active');
 		});
+
+		it('allows to list units', async () => {
+			const bus = await singleton();
+			const manager = new ServiceManager(bus);
+
+			const units = await manager.listUnits();
+			expect(units.map((u) => u.name)).to.include('dummy.service');
+
+			const dummy = await manager.listUnits({ patterns: ['dummy.*'] });
+			expect(dummy.map((u) => u.name)).to.deep.equal(['dummy.service']);
+			expect(dummy[0].activeState).to.equal(
+				await manager.getUnit('dummy.service').activeState,
+			);
+
+			const active = await manager.listUnits({ states: ['active'] });
+			expect(active.every((u) => u.activeState === 'active')).to.equal(true);
+		});
+
+		it('allows to query unit files', async () => {
+			const bus = await singleton();
+			const manager = new ServiceManager(bus);
+
+			const files = await manager.listUnitFiles({ patterns: ['dummy.*'] });
+			expect(files.map((f) => f.path)).to.satisfy((paths: string[]) =>
+				paths.every((path) => path.endsWith('/dummy.service')),
+			);
+			await expect(manager.getUnitFileState('dummy.service')).to.eventually.be
+				.a('string');
+			await expect(
+				manager.getUnitFileState('unknown.service'),
+			).to.be.rejectedWith(MethodError);
+		});
+
+		it('allows to enable and disable unit files at runtime', async () => {
+			const bus = await singleton();
+			const manager = new ServiceManager(bus);
+
+			const enabled = await manager.enableUnitFiles(['dummy.service'], {
+				runtime: true,
+			});
+			expect(enabled.changes).to.be.an('array');
+			const disabled = await manager.disableUnitFiles(['dummy.service'], {
+				runtime: true,
+			});
+			expect(disabled).to.be.an('array');
+		});
+
+		it('allows to start a transient unit and wait for it', async () => {
+			const bus = await singleton();
+			const manager = new ServiceManager(bus);
+
+			const job = await manager.startTransientUnit(
+				'transient-test.service',
+				{ execStart: ['/bin/true'], type: 'oneshot' },
+				{ wait: true },
+			);
+			expect(job.path).to.match(/^\/org\/freedesktop\/systemd1\/job\//);
+		});
+
+		it('rejects isolating a target that does not exist', async () => {
+			const bus = await singleton();
+			const manager = new ServiceManager(bus);
+
+			await expect(manager.isolate('unknown.target')).to.be.rejectedWith(
+				MethodError,
+			);
+		});
+
+		it('allows to list jobs', async () => {
+			const bus = await singleton();
+			const manager = new ServiceManager(bus);
+			await expect(manager.listJobs()).to.eventually.be.an('array');
+		});
+
+		it('rejects invalid transient unit properties', async () => {
+			const bus = await singleton();
+			const manager = new ServiceManager(bus);
+
+			for (const properties of [
+				{ memoryMax: -1 },
+				{ memoryMax: 1.5 },
+				{ memoryMax: Infinity },
+				{ cpuQuota: -20 },
+				{ runtimeMaxSec: NaN },
+			]) {
+				await expect(
+					manager.startTransientUnit('invalid.service', {
+						execStart: ['/bin/true'],
+						...properties,
+					}),
+				).to.be.rejectedWith(RangeError);
+			}
+		});
+
+		it('rejects unknown signals and invalid signal values', async () => {
+			const bus = await singleton();
+			const unit = new ServiceManager(bus).getUnit('dummy.service');
+
+			await expect(unit.kill('SIGFOO')).to.be.rejectedWith(TypeError);
+			await expect(unit.kill('RTMIN+31')).to.be.rejectedWith(TypeError);
+			await expect(unit.kill(0)).to.be.rejectedWith(RangeError);
+			await expect(unit.kill(65)).to.be.rejectedWith(RangeError);
+			for (const value of [NaN, 0.5, 2 ** 31]) {
+				await expect(unit.queueSignal('SIGRTMIN', value)).to.be.rejectedWith(
+					RangeError,
+				);
+			}
+		});
+
+		it('rejects invalid job ids', async () => {
+			const bus = await singleton();
+			const manager = new ServiceManager(bus);
+
+			for (const id of [-1, 1.5, NaN, 2 ** 32]) {
+				await expect(manager.getJob(id)).to.be.rejectedWith(RangeError);
+				await expect(manager.cancelJob(id)).to.be.rejectedWith(RangeError);
+			}
+		});
+
+		it('allows to wait for jobs in a row on the same bus', async () => {
+			const bus = await singleton();
+			const unit = new ServiceManager(bus).getUnit('dummy.service');
+
+			await expect(unit.stop('fail', { wait: true })).to.not.be.rejected;
+			await expect(unit.activeState).to.eventually.equal('inactive');
+			await expect(unit.start('fail', { wait: true })).to.not.be.rejected;
+			await expect(unit.activeState).to.eventually.equal('active');
+		});
+
+		it('keeps other subscriptions running after one unsubscribes', async () => {
+			const bus = await singleton();
+			const unit = new ServiceManager(bus).getUnit('dummy.service');
+
+			const states: string[] = [];
+			const first = await unit.subscribe(() => void 0);
+			const second = await unit.subscribe(({ activeState }) =>
+				states.push(activeState),
+			);
+			await first.unsubscribe();
+
+			// Waiting for jobs relies on the same manager subscription
+			await expect(unit.stop('fail', { wait: true })).to.not.be.rejected;
+			await expect(unit.start('fail', { wait: true })).to.not.be.rejected;
+			await eventually(() => states.includes('active'));
+			await second.unsubscribe();
+
+			expect(states).to.include('inactive');
+			expect(states).to.include('active');
+		});
+	});
+
+	describe('connections', () => {
+		it('connect allows to use a bus by address', async () => {
+			const bus = await connect(
+				process.env.DBUS_SYSTEM_BUS_ADDRESS ??
+					'unix:path=/run/dbus/system_bus_socket',
+			);
+			try {
+				await expect(
+					new ServiceManager(bus).getUnit('dummy.service').activeState,
+				).to.not.be.rejected;
+			} finally {
+				close(bus);
+			}
+		});
+
+		it('connect rejects with a TransportError for unreachable addresses', async () => {
+			await expect(
+				connect('unix:path=/nonexistent/bus_socket'),
+			).to.be.rejectedWith(TransportError);
+		});
+
+		it('close interrupts pending calls and rejects later ones', async () => {
+			const bus = await system();
+			const unit = new ServiceManager(bus).getUnit('dummy.service');
+
+			const pending = unit.restart('fail', { wait: true });
+			close(bus);
+			await expect(pending).to.be.rejectedWith(TransportError);
+			await expect(unit.activeState).to.be.rejectedWith(TransportError);
+		});
+
+		it('peer rejects with a TransportError if the socket is missing', async () => {
+			await expect(peer('/nonexistent/systemd/private')).to.be.rejectedWith(
+				TransportError,
+			);
+		});
 	});
 });
@@ -8,14 +8,321 @@ declare module "*index.node" {
 		private constructor();
 	};
 
+	class Subscription {
+		// Needed for typechecking
+		private __id: unique symbol
+
+		// Do not allow direct instantiation
+		// or sub-classing
+		private constructor();
+	};
+
+	class Inhibitor {
+		// Needed for typechecking
+		private __id: unique symbol
+
+		// Do not allow direct instantiation
+		// or sub-classing
+		private constructor();
+	};
+
+	class AbortHandle {
+		// Needed for typechecking
+		private __id: unique symbol
+
+		// Do not allow direct instantiation
+		// or sub-classing
+		private constructor();
+	};
+
+	interface CallOptions {
+		timeoutMs?: number;
+		abort?: AbortHandle;
+	}
+
+	interface InhibitorInfo {
+		what: string;
+		who: string;
+		why: string;
+		mode: string;
+		uid: number;
+		pid: number;
+	}
+
 	function system(): Promise<SystemBus>;
+	function session(): Promise<SystemBus>;
+	function connect(address: string): Promise<SystemBus>;
+	function peer(path: string): Promise<SystemBus>;
+	function close(bus: SystemBus): void;
+	function setConnectionListener(bus: SystemBus, listener: (event: string) => void): void;
+	function setErrorClasses(
+		methodError: new (message: string, code: string) => Error,
+		transportError: new (message: string) => Error,
+		abortError: new (message: string) => Error,
+		timeoutError: new (message: string) => Error,
+	): void;
+	function abortHandle(): AbortHandle;
+	function abort(handle: AbortHandle): void;
+
+	interface UnitStatus {
+		name: string;
+		description: string;
+		loadState: string;
+		activeState: string;
+		subState: string;
+		followed: string;
+		path: string;
+		job: { id: number; type: string; path: string } | null;
+	}
+
+	interface UnitFileChange {
+		type: string;
+		file: string;
+		destination: string;
+	}
+
+	interface UnitFileInstall {
+		carriesInstallInfo: boolean;
+		changes: UnitFileChange[];
+	}
+
+	/**
+	 * Properties of a transient unit
+	 *
+	 * See: https://www.freedesktop.org/software/systemd/man/systemd.resource-control.html
+	 */
+	interface TransientProperties {
+		/** Description of the unit */
+		description?: string;
+
+		/** Service type, e.g. `simple`, `exec` or `oneshot` */
+		type?: string;
+
+		/** Command to execute, the first element must be the absolute path to the binary */
+		execStart?: string[];
+
+		/** Environment variables for the executed process */
+		environment?: Record<string, string>;
+
+		/** Working directory for the executed process */
+		workingDirectory?: string;
+
+		/** User to run the process as */
+		user?: string;
+
+		/** Maximum memory usage in bytes, i.e. `MemoryMax=` */
+		memoryMax?: number;
+
+		/** Maximum CPU time as a percentage of one CPU, i.e. `CPUQuota=` */
+		cpuQuota?: number;
+
+		/** Maximum time in seconds the unit is allowed to run, i.e. `RuntimeMaxSec=` */
+		runtimeMaxSec?: number;
+
+		/** Consider the service active after the process exits, i.e. `RemainAfterExit=` */
+		remainAfterExit?: boolean;
+	}
+
+	/**
+	 * Resource control properties that can be changed on a running unit, using
+	 * the D-Bus property names. Limits accept `Infinity` to remove the limit.
+	 *
+	 * See: https://www.freedesktop.org/software/systemd/man/systemd.resource-control.html
+	 */
+	interface SettableUnitProperties {
+		MemoryMin?: number;
+		MemoryLow?: number;
+		MemoryHigh?: number;
+		MemoryMax?: number;
+		MemorySwapMax?: number;
+		TasksMax?: number;
+
+		/** CPU time in microseconds per second, i.e. `CPUQuota=50%` is 500000 */
+		CPUQuotaPerSecUSec?: number;
+		CPUQuotaPeriodUSec?: number;
+		CPUWeight?: number;
+		StartupCPUWeight?: number;
+		IOWeight?: number;
+		StartupIOWeight?: number;
+		CPUAccounting?: boolean;
+		MemoryAccounting?: boolean;
+		TasksAccounting?: boolean;
+		IOAccounting?: boolean;
+		IPAccounting?: boolean;
+	}
+
+	interface UnitDependencies {
+		requires: string[] | null;
+		requisite: string[] | null;
+		wants: string[] | null;
+		bindsTo: string[] | null;
+		upholds: string[] | null;
+		partOf: string[] | null;
+		requiredBy: string[] | null;
+		requisiteOf: string[] | null;
+		wantedBy: string[] | null;
+		boundBy: string[] | null;
+		upheldBy: string[] | null;
+		consistsOf: string[] | null;
+		conflicts: string[] | null;
+		before: string[] | null;
+		after: string[] | null;
+		onFailure: string[] | null;
+		triggers: string[] | null;
+		triggeredBy: string[] | null;
+		propagatesReloadTo: string[] | null;
+	}
+
+	interface DependencyNode {
+		name: string;
+		/** `null` if the unit could not be read, e.g. because it is not loaded */
+		activeState: string | null;
+		children: DependencyNode[];
+	}
+
+	/** A job enqueued by the manager, `result` is only set when waiting for the job */
+	interface EnqueuedJob {
+		path: string;
+		result?: string;
+	}
+
+	interface JobInfo {
+		id: number;
+		unit: string;
+		jobType: string;
+		state: string;
+		path: string;
+		unitPath: string;
+	}
+
+	interface UnitProperties {
+		id: string | null;
+		names: string[] | null;
+		description: string | null;
+		loadState: string | null;
+		activeState: string | null;
+		subState: string | null;
+		unitFileState: string | null;
+		fragmentPath: string | null;
+		dropInPaths: string[] | null;
+		needDaemonReload: boolean | null;
+		invocationId: string | null;
+		stateChangeTimestamp: Date | null;
+		inactiveExitTimestamp: Date | null;
+		activeEnterTimestamp: Date | null;
+		activeExitTimestamp: Date | null;
+		inactiveEnterTimestamp: Date | null;
+	}
+
+	interface ExecCommand {
+		path: string;
+		argv: string[];
+		ignoreErrors: boolean;
+		startTimestamp: Date | null;
+		exitTimestamp: Date | null;
+		pid: number;
+		code: number;
+		status: number;
+	}
+
+	interface ServiceStatus {
+		mainPid: number;
+		controlPid: number;
+		result: string;
+		execMainStatus: number;
+		execMainCode: number;
+		execMainStartTimestamp: Date | null;
+		nRestarts: number;
+		statusText: string;
+		restart: string;
+		watchdogTimestamp: Date | null;
+		execStart: ExecCommand[];
+	}
+
+	// Counters are null if accounting is disabled for the unit or
+	// the value is not available
+	interface ResourceUsage {
+		memoryCurrent: number | null;
+		memoryPeak: number | null;
+		memorySwapCurrent: number | null;
+		/**
+		 * CPU time used in nanoseconds. This is converted to a number, so it
+		 * loses precision above 2^53 ns, i.e. about 104 days of CPU time
+		 */
+		cpuUsageNSec: number | null;
+		tasksCurrent: number | null;
+		ipIngressBytes: number | null;
+		ipEgressBytes: number | null;
+		ioReadBytes: number | null;
+		ioWriteBytes: number | null;
+	}
 
 	// These methods
-	function unitActiveState(bus: SystemBus, unitName: string): Promise<string>;
-	function unitPartOf(bus: SystemBus, unitName: string): Promise<string[]>;
-	function unitStart(bus: SystemBus, unitName: string, mode: string): Promise<void>;
-	function unitStop(bus: SystemBus, unitName: string, mode: string): Promise<void>;
-	function unitRestart(bus: SystemBus, unitName: string, mode: string): Promise<void>;
-	function reboot(bus: SystemBus, interactive: boolean): Promise<void>;
-	function powerOff(bus: SystemBus, interactive: boolean): Promise<void>;
+	function unitActiveState(bus: SystemBus, unitName: string, options?: CallOptions): Promise<string>;
+	function unitPartOf(bus: SystemBus, unitName: string, options?: CallOptions): Promise<string[]>;
+	function unitDependencies(bus: SystemBus, unitName: string, options?: CallOptions): Promise<UnitDependencies>;
+	function unitDependencyTree(bus: SystemBus, unitName: string, kind: string, reverse: boolean, depth?: number, options?: CallOptions): Promise<DependencyNode>;
+	function unitProperties(bus: SystemBus, unitName: string, options?: CallOptions): Promise<UnitProperties>;
+	function serviceStatus(bus: SystemBus, unitName: string, options?: CallOptions): Promise<ServiceStatus>;
+	function unitResourceUsage(bus: SystemBus, unitName: string, options?: CallOptions): Promise<ResourceUsage>;
+	function unitSubscribe(bus: SystemBus, unitName: string, callback: (state: { activeState: string; subState: string }) => void, options?: CallOptions): Promise<Subscription>;
+	function unsubscribe(subscription: Subscription): Promise<void>;
+	function listUnits(bus: SystemBus, states: string[], patterns: string[], options?: CallOptions): Promise<UnitStatus[]>;
+	function listUnitFiles(bus: SystemBus, states: string[], patterns: string[], options?: CallOptions): Promise<Array<{ path: string; state: string }>>;
+	function getUnitFileState(bus: SystemBus, file: string, options?: CallOptions): Promise<string>;
+	function getUnitFileLinks(bus: SystemBus, name: string, runtime: boolean, options?: CallOptions): Promise<string[]>;
+	function enableUnitFiles(bus: SystemBus, files: string[], runtime: boolean, force: boolean, options?: CallOptions): Promise<UnitFileInstall>;
+	function disableUnitFiles(bus: SystemBus, files: string[], runtime: boolean, options?: CallOptions): Promise<UnitFileChange[]>;
+	function reenableUnitFiles(bus: SystemBus, files: string[], runtime: boolean, force: boolean, options?: CallOptions): Promise<UnitFileInstall>;
+	function maskUnitFiles(bus: SystemBus, files: string[], runtime: boolean, force: boolean, options?: CallOptions): Promise<UnitFileChange[]>;
+	function unmaskUnitFiles(bus: SystemBus, files: string[], runtime: boolean, options?: CallOptions): Promise<UnitFileChange[]>;
+	function presetUnitFiles(bus: SystemBus, files: string[], runtime: boolean, force: boolean, options?: CallOptions): Promise<UnitFileInstall>;
+	function revertUnitFiles(bus: SystemBus, files: string[], options?: CallOptions): Promise<UnitFileChange[]>;
+	function getDefaultTarget(bus: SystemBus, options?: CallOptions): Promise<string>;
+	function setDefaultTarget(bus: SystemBus, target: string, force: boolean, options?: CallOptions): Promise<UnitFileChange[]>;
+	function isolate(bus: SystemBus, target: string, options?: CallOptions): Promise<{ result: string; stopped: string[] }>;
+	function managerReload(bus: SystemBus, options?: CallOptions): Promise<void>;
+	function managerReexecute(bus: SystemBus, options?: CallOptions): Promise<void>;
+	function setUnitProperties(bus: SystemBus, unitName: string, runtime: boolean, properties: SettableUnitProperties, options?: CallOptions): Promise<void>;
+	function startTransientUnit(bus: SystemBus, unitName: string, mode: string, properties: TransientProperties, aux: Array<{ name: string; properties: TransientProperties }>, wait: boolean, options?: CallOptions): Promise<EnqueuedJob>;
+	function unitStart(bus: SystemBus, unitName: string, mode: string, wait: boolean, options?: CallOptions): Promise<EnqueuedJob>;
+	function unitStop(bus: SystemBus, unitName: string, mode: string, wait: boolean, options?: CallOptions): Promise<EnqueuedJob>;
+	function unitRestart(bus: SystemBus, unitName: string, mode: string, wait: boolean, options?: CallOptions): Promise<EnqueuedJob>;
+	function unitReload(bus: SystemBus, unitName: string, mode: string, wait: boolean, options?: CallOptions): Promise<EnqueuedJob>;
+	function unitTryRestart(bus: SystemBus, unitName: string, mode: string, wait: boolean, options?: CallOptions): Promise<EnqueuedJob>;
+	function unitReloadOrRestart(bus: SystemBus, unitName: string, mode: string, wait: boolean, options?: CallOptions): Promise<EnqueuedJob>;
+	function unitReloadOrTryRestart(bus: SystemBus, unitName: string, mode: string, wait: boolean, options?: CallOptions): Promise<EnqueuedJob>;
+	function unitKill(bus: SystemBus, unitName: string, whom: string, signal: string | number, options?: CallOptions): Promise<void>;
+	function unitQueueSignal(bus: SystemBus, unitName: string, whom: string, signal: string | number, value: number, options?: CallOptions): Promise<void>;
+	function unitResetFailed(bus: SystemBus, unitName: string, options?: CallOptions): Promise<void>;
+	function managerResetFailed(bus: SystemBus, options?: CallOptions): Promise<void>;
+	function listJobs(bus: SystemBus, options?: CallOptions): Promise<JobInfo[]>;
+	function getJob(bus: SystemBus, id: number, options?: CallOptions): Promise<string>;
+	function cancelJob(bus: SystemBus, id: number, options?: CallOptions): Promise<void>;
+	function clearJobs(bus: SystemBus, options?: CallOptions): Promise<void>;
+	function jobProperties(bus: SystemBus, path: string, options?: CallOptions): Promise<JobInfo>;
+	function jobCancel(bus: SystemBus, path: string, options?: CallOptions): Promise<void>;
+	function jobAfter(bus: SystemBus, path: string, options?: CallOptions): Promise<JobInfo[]>;
+	function jobBefore(bus: SystemBus, path: string, options?: CallOptions): Promise<JobInfo[]>;
+	function reboot(bus: SystemBus, interactive: boolean, options?: CallOptions): Promise<void>;
+	function powerOff(bus: SystemBus, interactive: boolean, options?: CallOptions): Promise<void>;
+	function halt(bus: SystemBus, interactive: boolean, options?: CallOptions): Promise<void>;
+	function suspend(bus: SystemBus, interactive: boolean, options?: CallOptions): Promise<void>;
+	function hibernate(bus: SystemBus, interactive: boolean, options?: CallOptions): Promise<void>;
+	function hybridSleep(bus: SystemBus, interactive: boolean, options?: CallOptions): Promise<void>;
+	function suspendThenHibernate(bus: SystemBus, interactive: boolean, options?: CallOptions): Promise<void>;
+	function inhibit(bus: SystemBus, what: string, who: string, why: string, mode: string, options?: CallOptions): Promise<Inhibitor>;
+	function inhibitorRelease(inhibitor: Inhibitor): boolean;
+	function listInhibitors(bus: SystemBus, options?: CallOptions): Promise<InhibitorInfo[]>;
+	function scheduleShutdown(bus: SystemBus, type: string, timeMs: number, options?: CallOptions): Promise<void>;
+	function cancelScheduledShutdown(bus: SystemBus, options?: CallOptions): Promise<boolean>;
+	function setWallMessage(bus: SystemBus, message: string, enable: boolean, options?: CallOptions): Promise<void>;
+	function scheduledShutdown(bus: SystemBus, options?: CallOptions): Promise<{ type: string; time: Date } | null>;
+	function canReboot(bus: SystemBus, options?: CallOptions): Promise<string>;
+	function canPowerOff(bus: SystemBus, options?: CallOptions): Promise<string>;
+	function canHalt(bus: SystemBus, options?: CallOptions): Promise<string>;
+	function canSuspend(bus: SystemBus, options?: CallOptions): Promise<string>;
+	function canHibernate(bus: SystemBus, options?: CallOptions): Promise<string>;
+	function canHybridSleep(bus: SystemBus, options?: CallOptions): Promise<string>;
+	function canSuspendThenHibernate(bus: SystemBus, options?: CallOptions): Promise<string>;
 }